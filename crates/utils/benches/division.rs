@@ -0,0 +1,69 @@
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use utils::math::{div_rem, Divider};
+
+fn numbers() -> Vec<(u64, u32)> {
+    (0..10_000u64)
+        .map(|i| {
+            (
+                i.wrapping_mul(0x9e37_79b9_7f4a_7c15) >> 8,
+                1 + (i % 12) as u32,
+            )
+        })
+        .collect()
+}
+
+fn powers_of_ten(c: &mut Criterion) {
+    // Divisors are computed up front in both cases, only the division is timed.
+    let splits: Vec<(u64, u64)> = numbers()
+        .into_iter()
+        .map(|(n, exp)| (n, 10u64.pow(exp)))
+        .collect();
+    let dividers: Vec<(u64, Divider)> = numbers()
+        .into_iter()
+        .map(|(n, exp)| (n, Divider::power_of_ten(exp)))
+        .collect();
+    let mut group = c.benchmark_group("powers_of_ten");
+    group.bench_function("operators", |b| {
+        b.iter(|| {
+            black_box(&splits)
+                .iter()
+                .fold(0, |acc, &(n, split)| acc ^ (n / split) ^ (n % split))
+        })
+    });
+    group.bench_function("divider", |b| {
+        b.iter(|| {
+            black_box(&dividers).iter().fold(0, |acc, (n, divider)| {
+                let (q, r) = divider.div_rem(*n);
+                acc ^ q ^ r
+            })
+        })
+    });
+    group.finish();
+}
+
+fn small_divisors(c: &mut Criterion) {
+    let numbers = numbers();
+    let mut group = c.benchmark_group("small_divisors");
+    group.bench_function("operators", |b| {
+        b.iter(|| {
+            numbers.iter().fold(0, |acc, &(n, d)| {
+                let d = black_box(d as u64);
+                acc ^ (n / d) ^ (n % d)
+            })
+        })
+    });
+    group.bench_function("div_rem", |b| {
+        b.iter(|| {
+            numbers.iter().fold(0, |acc, &(n, d)| {
+                let (q, r) = div_rem(n, black_box(d as u64)).unwrap_or_default();
+                acc ^ q ^ r
+            })
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, powers_of_ten, small_divisors);
+criterion_main!(benches);
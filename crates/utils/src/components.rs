@@ -0,0 +1,162 @@
+use crate::{disjoint_set::DisjointSet, grid::Grid, movement::Movement, position::Position};
+
+/// Index of a region in [`Components::regions`].
+pub type RegionId = usize;
+
+/// Which cells are considered adjacent when growing regions.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Connectivity {
+    /// Orthogonal neighbours only.
+    Four,
+    /// Orthogonal and diagonal neighbours.
+    Eight,
+}
+
+impl Connectivity {
+    /// Movements to the neighbours already visited by a row-major scan.
+    fn backward(self) -> &'static [Movement] {
+        const FOUR: [Movement; 2] = [Movement { x: -1, y: 0 }, Movement { x: 0, y: -1 }];
+        const EIGHT: [Movement; 4] = [
+            Movement { x: -1, y: 0 },
+            Movement { x: -1, y: -1 },
+            Movement { x: 0, y: -1 },
+            Movement { x: 1, y: -1 },
+        ];
+        match self {
+            Connectivity::Four => &FOUR,
+            Connectivity::Eight => &EIGHT,
+        }
+    }
+}
+
+/// Summary of a connected set of equal cells.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Region<T> {
+    pub value: T,
+    pub cells: usize,
+    /// Top-left corner of the bounding box.
+    pub min: Position,
+    /// Bottom-right corner of the bounding box, included.
+    pub max: Position,
+}
+
+/// Connected regions of a grid.
+#[derive(Clone, Debug)]
+pub struct Components<T> {
+    /// Region of every cell.
+    pub labels: Grid<RegionId>,
+    /// Regions, in row-major order of their first cell.
+    pub regions: Vec<Region<T>>,
+}
+
+impl<T> Components<T> {
+    /// Positions of the cells of region `id`, in row-major order.
+    pub fn cells(&self, id: RegionId) -> impl Iterator<Item = Position> + '_ {
+        self.labels
+            .positions()
+            .filter(move |&(_, &label)| label == id)
+            .map(|(p, _)| p)
+    }
+}
+
+impl<T: Eq + Clone> Grid<T> {
+    /// Label the regions made of adjacent cells holding equal values.
+    pub fn components(&self, connectivity: Connectivity) -> Components<T> {
+        let index = |p: Position| p.y * self.width + p.x;
+        let mut sets = DisjointSet::new(self.width * self.height);
+        for (p, value) in self.positions() {
+            for n in connectivity
+                .backward()
+                .iter()
+                .filter_map(|m| self.checked_move(p, *m))
+            {
+                if self[&n] == *value {
+                    sets.union(index(p), index(n));
+                }
+            }
+        }
+
+        let mut ids: Vec<Option<RegionId>> = vec![None; sets.len()];
+        let mut regions: Vec<Region<T>> = Vec::with_capacity(sets.sets());
+        let labels = Grid::from_fn(self.width, self.height, |p| {
+            let root = sets.find(index(p));
+            match ids[root] {
+                Some(id) => {
+                    let region = &mut regions[id];
+                    region.cells += 1;
+                    region.min.x = region.min.x.min(p.x);
+                    region.max.x = region.max.x.max(p.x);
+                    region.max.y = p.y;
+                    id
+                }
+                None => {
+                    ids[root] = Some(regions.len());
+                    regions.push(Region {
+                        value: self[&p].clone(),
+                        cells: 1,
+                        min: p,
+                        max: p,
+                    });
+                    regions.len() - 1
+                }
+            }
+        });
+        Components { labels, regions }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    const INCLUSION: &str = "OOOOO
+OXOXO
+OOOOO
+OXOXO
+OOOOO";
+
+    const CHECKER: &str = "ab
+ba";
+
+    fn grid(input: &str) -> Grid<char> {
+        Grid::parse(input, Ok::<_, ()>).unwrap()
+    }
+
+    #[test_case(INCLUSION, Connectivity::Four => 5)]
+    #[test_case(INCLUSION, Connectivity::Eight => 5)]
+    #[test_case(CHECKER, Connectivity::Four => 4)]
+    #[test_case(CHECKER, Connectivity::Eight => 2)]
+    fn count(input: &str, connectivity: Connectivity) -> usize {
+        grid(input).components(connectivity).regions.len()
+    }
+
+    #[test]
+    fn regions() {
+        let components = grid(INCLUSION).components(Connectivity::Four);
+        let outer = &components.regions[0];
+        assert_eq!(outer.value, 'O');
+        assert_eq!(outer.cells, 21);
+        assert_eq!(
+            (outer.min, outer.max),
+            (Position { x: 0, y: 0 }, Position { x: 4, y: 4 })
+        );
+        let last = &components.regions[4];
+        assert_eq!((last.value, last.cells), ('X', 1));
+        assert_eq!(last.min, Position { x: 3, y: 3 });
+        assert_eq!(components.labels[&Position { x: 3, y: 3 }], 4);
+        assert_eq!(components.cells(4).collect::<Vec<_>>(), [last.min]);
+    }
+
+    #[test]
+    fn bounding_box() {
+        // The region reaches its leftmost column after its first row.
+        let components = grid("BAA\nAAB").components(Connectivity::Four);
+        let a = &components.regions[1];
+        assert_eq!(a.cells, 4);
+        assert_eq!(
+            (a.min, a.max),
+            (Position { x: 0, y: 0 }, Position { x: 2, y: 1 })
+        );
+    }
+}
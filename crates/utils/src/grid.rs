@@ -1,35 +1,183 @@
-use crate::{movement::Movement, position::Position};
+use crate::{
+    movement::{Movement, ALL8, CLOCKWISE},
+    position::Position,
+    rect::Rect,
+};
+pub use bits::{BitGrid, DirectionBitGrid};
+pub use sparse::SparseGrid;
 use std::{
+    convert::Infallible,
+    error::Error,
     fmt::Display,
     ops::{Index, IndexMut},
+    str::FromStr,
 };
+pub use torus::TorusGrid;
+pub use view::{Diagonal, SubGrid, Windows};
+
+mod bits;
+#[cfg(feature = "rayon")]
+mod par;
+mod sparse;
+mod torus;
+mod view;
 
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Grid<T> {
     pub width: usize,
     pub height: usize,
     data: Vec<T>,
 }
 
+/// Errors raised while building a grid from rows or text.
+///
+/// Lines and columns are 1-based, as they would be displayed by an editor.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GridError<E = Infallible> {
+    /// The input does not contain any row or its first row is empty.
+    Empty,
+    /// A row does not have the same length as the first one.
+    Ragged {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// A cell was rejected by the conversion closure.
+    Cell {
+        line: usize,
+        column: usize,
+        error: E,
+    },
+}
+
+impl<E: Display> Display for GridError<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GridError::Empty => write!(f, "grid is empty"),
+            GridError::Ragged {
+                line,
+                expected,
+                found,
+            } => write!(f, "line {line}: expected {expected} cells, found {found}"),
+            GridError::Cell {
+                line,
+                column,
+                error,
+            } => write!(f, "line {line}, column {column}: {error}"),
+        }
+    }
+}
+
+impl<E: Error> Error for GridError<E> {}
+
 impl<T> Grid<T> {
-    /// Create a grid from its dimensions.
-    pub fn new(width: usize, height: usize) -> Self {
+    /// Create a grid from its dimensions, filled with default values.
+    pub fn new(width: usize, height: usize) -> Self
+    where
+        T: Default,
+    {
+        Self::from_fn(width, height, |_| T::default())
+    }
+
+    /// Create a grid from its dimensions, with every cell set to `value`.
+    pub fn filled(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
         Self {
             width,
             height,
-            data: Vec::with_capacity(width * height),
+            data: vec![value; width * height],
+        }
+    }
+
+    /// Create a grid from its dimensions, computing each cell from its position.
+    pub fn from_fn<F>(width: usize, height: usize, mut f: F) -> Self
+    where
+        F: FnMut(Position) -> T,
+    {
+        let data = (0..height)
+            .flat_map(|y| (0..width).map(move |x| Position { x, y }))
+            .map(&mut f)
+            .collect();
+        Self {
+            width,
+            height,
+            data,
         }
     }
 
     /// Create a grid from Vec of rows.
-    pub fn from_vec(input: Vec<Vec<T>>) -> Self {
+    ///
+    /// Fails if there is no row, if rows are empty or if they differ in length.
+    pub fn from_vec(input: Vec<Vec<T>>) -> Result<Self, GridError> {
         let height = input.len();
-        let width = input[0].len();
-        let data = input.into_iter().flatten().collect();
-        Self {
+        let width = input.first().map(Vec::len).unwrap_or_default();
+        if width == 0 {
+            return Err(GridError::Empty);
+        }
+        let mut data = Vec::with_capacity(width * height);
+        for (y, row) in input.into_iter().enumerate() {
+            if row.len() != width {
+                return Err(GridError::Ragged {
+                    line: y + 1,
+                    expected: width,
+                    found: row.len(),
+                });
+            }
+            data.extend(row);
+        }
+        Ok(Self {
             width,
             height,
             data,
+        })
+    }
+
+    /// Create a grid from text, one row per line, converting each character with `f`.
+    ///
+    /// Trailing blank lines are ignored. Fails if the input or its first line is
+    /// empty, if lines differ in length or if `f` rejects a character.
+    pub fn parse<E, F>(input: &str, mut f: F) -> Result<Self, GridError<E>>
+    where
+        F: FnMut(char) -> Result<T, E>,
+    {
+        let mut width = 0;
+        let mut height = 0;
+        let mut data = Vec::with_capacity(input.len());
+        for (y, line) in input.trim_end_matches(['\n', '\r']).lines().enumerate() {
+            let start = data.len();
+            for (x, chr) in line.chars().enumerate() {
+                let cell = f(chr).map_err(|error| GridError::Cell {
+                    line: y + 1,
+                    column: x + 1,
+                    error,
+                })?;
+                data.push(cell);
+            }
+            let found = data.len() - start;
+            if y == 0 {
+                if found == 0 {
+                    return Err(GridError::Empty);
+                }
+                width = found;
+            } else if found != width {
+                return Err(GridError::Ragged {
+                    line: y + 1,
+                    expected: width,
+                    found,
+                });
+            }
+            height += 1;
+        }
+        if height == 0 {
+            return Err(GridError::Empty);
         }
+        Ok(Self {
+            width,
+            height,
+            data,
+        })
     }
 
     pub fn contains(&self, position: Position) -> bool {
@@ -40,11 +188,345 @@ impl<T> Grid<T> {
         PosIter::new(self)
     }
 
+    /// Position of a cell from its index in `data`.
+    fn position(&self, index: usize) -> Position {
+        Position {
+            x: index % self.width,
+            y: index / self.width,
+        }
+    }
+
+    /// Cells in row-major order.
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.data.iter()
+    }
+
+    /// Mutable cells in row-major order.
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, T> {
+        self.data.iter_mut()
+    }
+
+    /// Mutable cells with their position, in row-major order.
+    pub fn positions_mut(&mut self) -> impl Iterator<Item = (Position, &mut T)> {
+        let width = self.width;
+        self.data.iter_mut().enumerate().map(move |(i, value)| {
+            let position = Position {
+                x: i % width,
+                y: i / width,
+            };
+            (position, value)
+        })
+    }
+
+    /// Grid of the same dimensions, with `f` applied to every cell.
+    pub fn map<U, F>(&self, f: F) -> Grid<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        Grid {
+            width: self.width,
+            height: self.height,
+            data: self.data.iter().map(f).collect(),
+        }
+    }
+
+    /// Grid combining the cells of two grids of the same dimensions.
+    pub fn zip_with<U, V, F>(&self, other: &Grid<U>, mut f: F) -> Grid<V>
+    where
+        F: FnMut(&T, &U) -> V,
+    {
+        assert_eq!(
+            (self.width, self.height),
+            (other.width, other.height),
+            "grids dimensions differ"
+        );
+        Grid {
+            width: self.width,
+            height: self.height,
+            data: self
+                .data
+                .iter()
+                .zip(&other.data)
+                .map(|(a, b)| f(a, b))
+                .collect(),
+        }
+    }
+
+    /// Number of cells satisfying `predicate`.
+    pub fn count_where<P>(&self, mut predicate: P) -> usize
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.data.iter().filter(|v| predicate(v)).count()
+    }
+
+    /// Position of the first cell, in row-major order, satisfying `predicate`.
+    pub fn find<P>(&self, predicate: P) -> Option<Position>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.data
+            .iter()
+            .position(predicate)
+            .map(|i| self.position(i))
+    }
+
+    /// Positions of every cell satisfying `predicate`, in row-major order.
+    pub fn find_all<'a, P>(&'a self, mut predicate: P) -> impl Iterator<Item = Position> + 'a
+    where
+        P: FnMut(&T) -> bool + 'a,
+    {
+        self.data
+            .iter()
+            .enumerate()
+            .filter(move |(_, v)| predicate(v))
+            .map(|(i, _)| self.position(i))
+    }
+
+    pub fn get(&self, position: Position) -> Option<&T> {
+        self.contains(position).then(|| &self[&position])
+    }
+
+    pub fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        self.contains(position).then(|| &mut self[&position])
+    }
+
+    /// Cells of row `y`, left to right.
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.data[y * self.width..(y + 1) * self.width]
+    }
+
+    /// Rows of the grid, top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.data.chunks_exact(self.width.max(1))
+    }
+
+    /// Cells of column `x`, top to bottom.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column out of bounds");
+        self.data[x..].iter().step_by(self.width)
+    }
+
+    /// Columns of the grid, left to right.
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Cells from `start` going down and to the right until the edge of the grid.
+    pub fn diagonal(&self, start: Position) -> Diagonal<'_, T> {
+        Diagonal::down_right(self, start)
+    }
+
+    /// Cells from `start` going down and to the left until the edge of the grid.
+    pub fn anti_diagonal(&self, start: Position) -> Diagonal<'_, T> {
+        Diagonal::down_left(self, start)
+    }
+
+    /// Every down-right diagonal, from the top-right corner to the bottom-left one.
+    pub fn diagonals(&self) -> impl Iterator<Item = Diagonal<'_, T>> {
+        let (width, height) = self.non_empty_size();
+        let top = (0..width).rev().map(|x| Position { x, y: 0 });
+        let left = (1..height).map(|y| Position { x: 0, y });
+        top.chain(left).map(|start| self.diagonal(start))
+    }
+
+    /// Every down-left diagonal, from the top-left corner to the bottom-right one.
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = Diagonal<'_, T>> {
+        let (width, height) = self.non_empty_size();
+        let top = (0..width).map(|x| Position { x, y: 0 });
+        let right = (1..height).map(move |y| Position { x: width - 1, y });
+        top.chain(right).map(|start| self.anti_diagonal(start))
+    }
+
+    /// Dimensions, both zero when the grid has no cell.
+    fn non_empty_size(&self) -> (usize, usize) {
+        if self.data.is_empty() {
+            (0, 0)
+        } else {
+            (self.width, self.height)
+        }
+    }
+
+    /// Every `width` x `height` view of the grid, in row-major order.
+    pub fn windows(&self, width: usize, height: usize) -> Windows<'_, T> {
+        Windows::new(self, width, height)
+    }
+
+    /// Rectangle covering the whole grid.
+    pub fn bounds(&self) -> Rect {
+        Rect::sized(self.width, self.height)
+    }
+
+    /// View of the `width` x `height` rectangle whose top-left corner is `origin`,
+    /// if it fits inside the grid.
+    pub fn sub_grid(
+        &self,
+        origin: Position,
+        width: usize,
+        height: usize,
+    ) -> Option<SubGrid<'_, T>> {
+        (origin.x + width <= self.width && origin.y + height <= self.height)
+            .then(|| SubGrid::new(self, origin, width, height))
+    }
+
+    /// Copy of the grid turned a quarter clockwise.
+    pub fn rotate_cw(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(self.height, self.width, |p| {
+            self[&Position {
+                x: p.y,
+                y: self.height - 1 - p.x,
+            }]
+                .clone()
+        })
+    }
+
+    /// Copy of the grid turned a quarter counter-clockwise.
+    pub fn rotate_ccw(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(self.height, self.width, |p| {
+            self[&Position {
+                x: self.width - 1 - p.y,
+                y: p.x,
+            }]
+                .clone()
+        })
+    }
+
+    /// Copy of the grid mirrored along its main diagonal, rows become columns.
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(self.height, self.width, |p| {
+            self[&Position { x: p.y, y: p.x }].clone()
+        })
+    }
+
+    /// Copy of the grid mirrored left to right.
+    pub fn flip_h(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(self.width, self.height, |p| {
+            self[&Position {
+                x: self.width - 1 - p.x,
+                y: p.y,
+            }]
+                .clone()
+        })
+    }
+
+    /// Copy of the grid mirrored top to bottom.
+    pub fn flip_v(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(self.width, self.height, |p| {
+            self[&Position {
+                x: p.x,
+                y: self.height - 1 - p.y,
+            }]
+                .clone()
+        })
+    }
+
+    /// Positions met stepping from `start` by `movement`, `start` excluded, until
+    /// leaving the grid.
+    pub fn ray(&self, start: Position, movement: Movement) -> Ray<'_, T> {
+        Ray {
+            grid: self,
+            current: start,
+            movement,
+        }
+    }
+
+    /// First position along the ray from `start` whose cell satisfies `predicate`,
+    /// with the number of steps taken to reach it.
+    ///
+    /// Returns `None` when the ray leaves the grid first.
+    pub fn cast_until<P>(
+        &self,
+        start: Position,
+        movement: Movement,
+        mut predicate: P,
+    ) -> Option<(Position, usize)>
+    where
+        P: FnMut(Position, &T) -> bool,
+    {
+        self.ray(start, movement)
+            .zip(1..)
+            .find(|&(p, _)| predicate(p, &self[&p]))
+    }
+
     pub fn checked_move(&self, position: Position, movement: Movement) -> Option<Position> {
         position
             .checked_move(movement)
             .filter(|n| n.x < self.width && n.y < self.height)
     }
+
+    /// Orthogonal neighbours of `position` inside the grid, clockwise from up.
+    pub fn neighbors4(&self, position: Position) -> Neighbors<'_, 'static, T> {
+        self.neighbors_with(position, &CLOCKWISE)
+    }
+
+    /// Orthogonal and diagonal neighbours of `position` inside the grid, clockwise
+    /// from up.
+    pub fn neighbors8(&self, position: Position) -> Neighbors<'_, 'static, T> {
+        self.neighbors_with(position, &ALL8)
+    }
+
+    /// Cells reached from `position` with each of `movements`, skipping the ones
+    /// outside the grid.
+    pub fn neighbors_with<'m>(
+        &self,
+        position: Position,
+        movements: &'m [Movement],
+    ) -> Neighbors<'_, 'm, T> {
+        Neighbors {
+            grid: self,
+            position,
+            movements: movements.iter(),
+        }
+    }
+
+    /// Orthogonal neighbours of `position` whose value satisfies `predicate`.
+    pub fn neighbors4_where<P>(
+        &self,
+        position: Position,
+        mut predicate: P,
+    ) -> impl Iterator<Item = (Position, &T)>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.neighbors4(position).filter(move |(_, v)| predicate(v))
+    }
+
+    /// Orthogonal and diagonal neighbours of `position` whose value satisfies
+    /// `predicate`.
+    pub fn neighbors8_where<P>(
+        &self,
+        position: Position,
+        mut predicate: P,
+    ) -> impl Iterator<Item = (Position, &T)>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.neighbors8(position).filter(move |(_, v)| predicate(v))
+    }
+}
+
+/// Parse one cell per character, see [`Grid::parse`].
+impl<T: TryFrom<char>> FromStr for Grid<T> {
+    type Err = GridError<T::Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Grid::parse(s, T::try_from)
+    }
 }
 
 impl<T> Index<&Position> for Grid<T> {
@@ -79,23 +561,57 @@ impl<'a, T> Iterator for PosIter<'a, T> {
         if self.current >= self.grid.data.len() {
             return None;
         }
-        let position = Position {
-            y: self.current / self.grid.height,
-            x: self.current % self.grid.height,
-        };
+        let position = self.grid.position(self.current);
         let value = &self.grid.data[self.current];
         self.current += 1;
         Some((position, value))
     }
 }
 
+/// Iterator over the cells reached from a position with a set of movements.
+pub struct Neighbors<'a, 'm, T> {
+    grid: &'a Grid<T>,
+    position: Position,
+    movements: std::slice::Iter<'m, Movement>,
+}
+
+impl<'a, T> Iterator for Neighbors<'a, '_, T> {
+    type Item = (Position, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.movements.by_ref().find_map(|movement| {
+            let neighbor = self.grid.checked_move(self.position, *movement)?;
+            Some((neighbor, &self.grid[&neighbor]))
+        })
+    }
+}
+
+/// Iterator over the positions met stepping in a fixed direction, see
+/// [`Grid::ray`].
+pub struct Ray<'a, T> {
+    grid: &'a Grid<T>,
+    current: Position,
+    movement: Movement,
+}
+
+impl<T> Iterator for Ray<'_, T> {
+    type Item = Position;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.movement == (Movement { x: 0, y: 0 }) {
+            return None;
+        }
+        self.current = self.grid.checked_move(self.current, self.movement)?;
+        Some(self.current)
+    }
+}
+
 impl<T> Display for Grid<T>
 where
     T: Display,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for y in 0..self.height {
-            let line = &self.data[y * self.width..(y + 1) * self.width];
+        for line in self.rows() {
             for elem in line {
                 write!(f, "{}", elem)?;
             }
@@ -104,3 +620,231 @@ where
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    fn chars(input: &str) -> Result<Grid<char>, GridError> {
+        Grid::parse(input, Ok)
+    }
+
+    #[test]
+    fn filled() {
+        let grid = Grid::filled(3, 2, 7u8);
+        assert_eq!(grid[&Position { x: 2, y: 1 }], 7);
+        assert_eq!(grid.positions().count(), 6);
+    }
+
+    #[test]
+    fn from_fn() {
+        let grid = Grid::from_fn(3, 2, |p| p.x + 10 * p.y);
+        assert_eq!(grid[&Position { x: 2, y: 0 }], 2);
+        assert_eq!(grid[&Position { x: 1, y: 1 }], 11);
+    }
+
+    #[test]
+    fn positions_non_square() {
+        let grid = chars("abc\ndef").unwrap();
+        let found: Vec<_> = grid.positions().map(|(p, c)| (p.x, p.y, *c)).collect();
+        assert_eq!(found[4], (1, 1, 'e'));
+        assert_eq!(found[5], (2, 1, 'f'));
+    }
+
+    #[test_case("ab\ncd\n" => Ok((2, 2)))]
+    #[test_case("abc" => Ok((3, 1)))]
+    #[test_case("" => Err(GridError::Empty))]
+    #[test_case("\n\n" => Err(GridError::Empty))]
+    #[test_case("ab\ncd\n\n" => Ok((2, 2)) ; "trailing blank line")]
+    #[test_case("ab\r\ncd\r\n\r\n" => Ok((2, 2)) ; "trailing blank crlf line")]
+    #[test_case("\nab" => Err(GridError::Empty) ; "leading blank line")]
+    #[test_case("ab\n\ncd" => Err(GridError::Ragged { line: 2, expected: 2, found: 0 }))]
+    #[test_case("ab\nc" => Err(GridError::Ragged { line: 2, expected: 2, found: 1 }))]
+    #[test_case("ab\ncd\nefg" => Err(GridError::Ragged { line: 3, expected: 2, found: 3 }))]
+    fn parse_shape(input: &str) -> Result<(usize, usize), GridError> {
+        chars(input).map(|g| (g.width, g.height))
+    }
+
+    #[test]
+    fn parse_cell_error() {
+        let grid = Grid::parse("12\n3x", |c| c.to_digit(10).ok_or(c));
+        assert_eq!(
+            grid,
+            Err(GridError::Cell {
+                line: 2,
+                column: 2,
+                error: 'x'
+            })
+        );
+    }
+
+    #[test]
+    fn lines() {
+        let grid = chars("abc\ndef").unwrap();
+        assert_eq!(grid.row(1), ['d', 'e', 'f']);
+        assert_eq!(grid.rows().count(), 2);
+        assert_eq!(grid.column(1).collect::<String>(), "be");
+        let columns: Vec<String> = grid.columns().map(|c| c.collect()).collect();
+        assert_eq!(columns, ["ad", "be", "cf"]);
+    }
+
+    #[test]
+    fn diagonals() {
+        let grid = chars("abc\ndef").unwrap();
+        let diagonals: Vec<String> = grid.diagonals().map(|d| d.collect()).collect();
+        assert_eq!(diagonals, ["c", "bf", "ae", "d"]);
+        let anti: Vec<String> = grid.anti_diagonals().map(|d| d.collect()).collect();
+        assert_eq!(anti, ["a", "bd", "ce", "f"]);
+        for grid in [Grid::filled(0, 2, 0u8), Grid::filled(3, 0, 0u8)] {
+            assert_eq!(grid.diagonals().count(), 0);
+            assert_eq!(grid.anti_diagonals().count(), 0);
+        }
+    }
+
+    #[test_case(2, 2 => vec!["abde", "bcef"])]
+    #[test_case(1, 2 => vec!["ad", "be", "cf"])]
+    #[test_case(3, 1 => vec!["abc", "def"])]
+    #[test_case(4, 1 => Vec::<String>::new())]
+    #[test_case(0, 1 => Vec::<String>::new())]
+    fn windows(width: usize, height: usize) -> Vec<String> {
+        let grid = chars("abc\ndef").unwrap();
+        grid.windows(width, height)
+            .map(|w| w.iter().collect())
+            .collect()
+    }
+
+    #[test_case(0, 0 => vec![(1, 0), (0, 1)])]
+    #[test_case(1, 1 => vec![(1, 0), (2, 1), (1, 2), (0, 1)])]
+    #[test_case(2, 2 => vec![(2, 1), (1, 2)])]
+    fn neighbors4(x: usize, y: usize) -> Vec<(usize, usize)> {
+        let grid = Grid::filled(3, 3, ());
+        grid.neighbors4(Position { x, y })
+            .map(|(p, _)| (p.x, p.y))
+            .collect()
+    }
+
+    #[test_case(0, 0 => vec![(1, 0), (1, 1), (0, 1)])]
+    #[test_case(1, 0 => vec![(2, 0), (2, 1), (1, 1), (0, 1), (0, 0)])]
+    #[test_case(1, 1 => vec![(1, 0), (2, 0), (2, 1), (2, 2), (1, 2), (0, 2), (0, 1), (0, 0)])]
+    fn neighbors8(x: usize, y: usize) -> Vec<(usize, usize)> {
+        let grid = Grid::filled(3, 3, ());
+        grid.neighbors8(Position { x, y })
+            .map(|(p, _)| (p.x, p.y))
+            .collect()
+    }
+
+    #[test]
+    fn neighbors_where() {
+        let grid = chars("aba\nbab\naba").unwrap();
+        let center = Position { x: 1, y: 1 };
+        assert_eq!(grid.neighbors4_where(center, |&c| c == 'a').count(), 0);
+        assert_eq!(grid.neighbors8_where(center, |&c| c == 'a').count(), 4);
+        let knight = [Movement { x: 1, y: 2 }, Movement { x: -2, y: 1 }];
+        let found: Vec<_> = grid
+            .neighbors_with(Position { x: 0, y: 0 }, &knight)
+            .collect();
+        assert_eq!(found, [(Position { x: 1, y: 2 }, &'b')]);
+    }
+
+    #[test_case(Grid::rotate_cw => "da\neb\nfc\n")]
+    #[test_case(Grid::rotate_ccw => "cf\nbe\nad\n")]
+    #[test_case(Grid::transpose => "ad\nbe\ncf\n")]
+    #[test_case(Grid::flip_h => "cba\nfed\n")]
+    #[test_case(Grid::flip_v => "def\nabc\n")]
+    fn transforms(transform: fn(&Grid<char>) -> Grid<char>) -> String {
+        transform(&chars("abc\ndef").unwrap()).to_string()
+    }
+
+    #[test]
+    fn rotations_compose() {
+        let grid = chars("abc\ndef").unwrap();
+        assert_eq!(grid.rotate_cw().rotate_ccw(), grid);
+        assert_eq!(grid.rotate_cw().rotate_cw(), grid.flip_h().flip_v());
+        assert_eq!(grid.transpose().flip_h(), grid.rotate_cw());
+    }
+
+    #[test]
+    fn sub_grid() {
+        let grid = chars("abcd\nefgh\nijkl").unwrap();
+        let view = grid.sub_grid(Position { x: 1, y: 1 }, 2, 2).unwrap();
+        assert_eq!(view[&Position { x: 1, y: 0 }], 'g');
+        assert_eq!(view.get(Position { x: 2, y: 0 }), None);
+        let found: Vec<_> = view.positions().map(|(p, c)| (p.x, p.y, *c)).collect();
+        assert_eq!(found, [(0, 0, 'f'), (1, 0, 'g'), (0, 1, 'j'), (1, 1, 'k')]);
+        assert_eq!(view.to_grid().to_string(), "fg\njk\n");
+        assert!(grid.sub_grid(Position { x: 3, y: 0 }, 2, 1).is_none());
+    }
+
+    #[test]
+    fn mutation() {
+        let mut grid = Grid::from_fn(3, 2, |p| p.x + 10 * p.y);
+        grid.iter_mut().for_each(|v| *v += 1);
+        for (p, v) in grid.positions_mut() {
+            if p.y == 1 {
+                *v *= 2;
+            }
+        }
+        assert_eq!(
+            grid.iter().copied().collect::<Vec<_>>(),
+            [1, 2, 3, 22, 24, 26]
+        );
+    }
+
+    #[test]
+    fn map_zip() {
+        let grid = chars("a.b\n.c.").unwrap();
+        let occupied = grid.map(|&c| c != '.');
+        assert_eq!(occupied.count_where(|&o| o), 3);
+        let ranks = Grid::from_fn(3, 2, |p| p.y * 3 + p.x);
+        let zipped = grid.zip_with(&ranks, |&c, &r| if c == '.' { r } else { 0 });
+        assert_eq!(zipped.to_string(), "010\n305\n");
+    }
+
+    #[test]
+    fn find() {
+        let grid = chars("..^\n0.0").unwrap();
+        assert_eq!(grid.find(|&c| c == '^'), Some(Position { x: 2, y: 0 }));
+        assert_eq!(grid.find(|&c| c == '#'), None);
+        let heads: Vec<_> = grid.find_all(|&c| c == '0').collect();
+        assert_eq!(heads, [Position { x: 0, y: 1 }, Position { x: 2, y: 1 }]);
+    }
+
+    #[test_case(0, 0, 1, 0 => vec![(1, 0), (2, 0), (3, 0)])]
+    #[test_case(0, 0, 2, 1 => vec![(2, 1)])]
+    #[test_case(3, 2, -1, -1 => vec![(2, 1), (1, 0)])]
+    #[test_case(3, 2, 0, 1 => Vec::<(usize, usize)>::new())]
+    #[test_case(1, 1, 0, 0 => Vec::<(usize, usize)>::new())]
+    fn ray(x: usize, y: usize, dx: isize, dy: isize) -> Vec<(usize, usize)> {
+        let grid = Grid::filled(4, 3, ());
+        grid.ray(Position { x, y }, Movement { x: dx, y: dy })
+            .map(|p| (p.x, p.y))
+            .collect()
+    }
+
+    #[test]
+    fn cast_until() {
+        let grid = chars("....#\n.....").unwrap();
+        let right = Movement { x: 1, y: 0 };
+        assert_eq!(
+            grid.cast_until(Position { x: 0, y: 0 }, right, |_, &c| c == '#'),
+            Some((Position { x: 4, y: 0 }, 4))
+        );
+        assert_eq!(
+            grid.cast_until(Position { x: 0, y: 1 }, right, |_, &c| c == '#'),
+            None
+        );
+        assert_eq!(
+            grid.cast_until(Position { x: 0, y: 1 }, right, |p, _| p.x == 2),
+            Some((Position { x: 2, y: 1 }, 2))
+        );
+    }
+
+    #[test_case(vec![] => Err(GridError::Empty))]
+    #[test_case(vec![vec![]] => Err(GridError::Empty))]
+    #[test_case(vec![vec![1, 2], vec![3]] => Err(GridError::Ragged { line: 2, expected: 2, found: 1 }))]
+    #[test_case(vec![vec![1, 2], vec![3, 4]] => Ok((2, 2)))]
+    fn from_vec(input: Vec<Vec<u8>>) -> Result<(usize, usize), GridError> {
+        Grid::from_vec(input).map(|g| (g.width, g.height))
+    }
+}
@@ -0,0 +1,276 @@
+use crate::{movement::Direction, position::Position};
+
+/// Set of positions inside a `width` x `height` rectangle, one bit per cell.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BitGrid {
+    pub width: usize,
+    pub height: usize,
+    words: Vec<u64>,
+}
+
+impl BitGrid {
+    /// Create an empty set.
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            words: vec![0; (width * height).div_ceil(64)],
+        }
+    }
+
+    fn bit(&self, position: Position) -> (usize, u64) {
+        assert!(
+            position.x < self.width && position.y < self.height,
+            "position outside of the grid"
+        );
+        let index = position.y * self.width + position.x;
+        (index / 64, 1 << (index % 64))
+    }
+
+    /// Add a position, returns false if it was already present.
+    pub fn insert(&mut self, position: Position) -> bool {
+        let (word, mask) = self.bit(position);
+        let absent = self.words[word] & mask == 0;
+        self.words[word] |= mask;
+        absent
+    }
+
+    /// Remove a position, returns false if it was absent.
+    pub fn remove(&mut self, position: Position) -> bool {
+        let (word, mask) = self.bit(position);
+        let present = self.words[word] & mask != 0;
+        self.words[word] &= !mask;
+        present
+    }
+
+    pub fn contains(&self, position: Position) -> bool {
+        let (word, mask) = self.bit(position);
+        self.words[word] & mask != 0
+    }
+
+    /// Remove every position.
+    pub fn clear(&mut self) {
+        self.words.fill(0);
+    }
+
+    /// Number of positions in the set.
+    pub fn count(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+
+    /// Add every position of `other`, both sets must have the same dimensions.
+    pub fn union_with(&mut self, other: &BitGrid) {
+        self.combine(other, |a, b| a | b);
+    }
+
+    /// Keep only the positions also in `other`, both sets must have the same
+    /// dimensions.
+    pub fn intersect_with(&mut self, other: &BitGrid) {
+        self.combine(other, |a, b| a & b);
+    }
+
+    /// Remove every position of `other`, both sets must have the same dimensions.
+    pub fn difference_with(&mut self, other: &BitGrid) {
+        self.combine(other, |a, b| a & !b);
+    }
+
+    fn combine(&mut self, other: &BitGrid, f: impl Fn(u64, u64) -> u64) {
+        assert_eq!(
+            (self.width, self.height),
+            (other.width, other.height),
+            "grids dimensions differ"
+        );
+        for (a, b) in self.words.iter_mut().zip(&other.words) {
+            *a = f(*a, *b);
+        }
+    }
+
+    /// Positions in the set, in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = Position> + '_ {
+        self.words.iter().enumerate().flat_map(move |(i, &word)| {
+            let mut word = word;
+            std::iter::from_fn(move || {
+                (word != 0).then(|| {
+                    let index = i * 64 + word.trailing_zeros() as usize;
+                    word &= word - 1;
+                    Position {
+                        x: index % self.width,
+                        y: index / self.width,
+                    }
+                })
+            })
+        })
+    }
+}
+
+impl Extend<Position> for BitGrid {
+    fn extend<I: IntoIterator<Item = Position>>(&mut self, iter: I) {
+        for position in iter {
+            self.insert(position);
+        }
+    }
+}
+
+/// Set of `(position, direction)` pairs, for the four orthogonal directions,
+/// four bits per cell.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DirectionBitGrid {
+    pub width: usize,
+    pub height: usize,
+    words: Vec<u64>,
+}
+
+impl DirectionBitGrid {
+    /// Create an empty set.
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            words: vec![0; (width * height).div_ceil(16)],
+        }
+    }
+
+    fn cell(&self, position: Position) -> (usize, u32) {
+        assert!(
+            position.x < self.width && position.y < self.height,
+            "position outside of the grid"
+        );
+        let index = position.y * self.width + position.x;
+        (index / 16, (index % 16) as u32 * 4)
+    }
+
+    fn bit(&self, position: Position, direction: Direction) -> (usize, u64) {
+        assert!(!direction.is_diagonal(), "direction is not orthogonal");
+        let (word, shift) = self.cell(position);
+        (word, 1 << (shift + direction.index() as u32))
+    }
+
+    /// Add a pair, returns false if it was already present.
+    pub fn insert(&mut self, position: Position, direction: Direction) -> bool {
+        let (word, mask) = self.bit(position, direction);
+        let absent = self.words[word] & mask == 0;
+        self.words[word] |= mask;
+        absent
+    }
+
+    /// Remove a pair, returns false if it was absent.
+    pub fn remove(&mut self, position: Position, direction: Direction) -> bool {
+        let (word, mask) = self.bit(position, direction);
+        let present = self.words[word] & mask != 0;
+        self.words[word] &= !mask;
+        present
+    }
+
+    pub fn contains(&self, position: Position, direction: Direction) -> bool {
+        let (word, mask) = self.bit(position, direction);
+        self.words[word] & mask != 0
+    }
+
+    /// Directions recorded on a cell, as a mask of [`Direction::bit`].
+    pub fn directions(&self, position: Position) -> u8 {
+        let (word, shift) = self.cell(position);
+        ((self.words[word] >> shift) & 0xf) as u8
+    }
+
+    /// Remove every pair.
+    pub fn clear(&mut self) {
+        self.words.fill(0);
+    }
+
+    /// Number of pairs in the set.
+    pub fn count(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+
+    /// Cells holding at least one direction.
+    pub fn positions(&self) -> BitGrid {
+        let mut out = BitGrid::new(self.width, self.height);
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let position = Position { x, y };
+                if self.directions(position) != 0 {
+                    out.insert(position);
+                }
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::movement::Direction::{North, South, West};
+
+    fn p(x: usize, y: usize) -> Position {
+        Position { x, y }
+    }
+
+    #[test]
+    fn insert_contains() {
+        let mut grid = BitGrid::new(13, 11);
+        assert!(grid.insert(p(12, 10)));
+        assert!(!grid.insert(p(12, 10)));
+        assert!(grid.insert(p(0, 5)));
+        assert!(grid.contains(p(0, 5)));
+        assert!(!grid.contains(p(1, 5)));
+        assert_eq!(grid.count(), 2);
+        assert!(grid.remove(p(0, 5)));
+        assert!(!grid.remove(p(0, 5)));
+        assert_eq!(grid.iter().collect::<Vec<_>>(), [p(12, 10)]);
+        grid.clear();
+        assert!(grid.is_empty());
+    }
+
+    #[test]
+    fn set_operations() {
+        let mut a = BitGrid::new(10, 10);
+        a.extend([p(0, 0), p(5, 5), p(9, 9)]);
+        let mut b = BitGrid::new(10, 10);
+        b.extend([p(5, 5), p(3, 7)]);
+
+        let mut union = a.clone();
+        union.union_with(&b);
+        assert_eq!(union.count(), 4);
+
+        let mut intersection = a.clone();
+        intersection.intersect_with(&b);
+        assert_eq!(intersection.iter().collect::<Vec<_>>(), [p(5, 5)]);
+
+        let mut difference = a;
+        difference.difference_with(&b);
+        assert_eq!(difference.iter().collect::<Vec<_>>(), [p(0, 0), p(9, 9)]);
+    }
+
+    #[test]
+    fn directions() {
+        let mut grid = DirectionBitGrid::new(5, 5);
+        assert!(grid.insert(p(4, 4), North));
+        assert!(grid.insert(p(4, 4), West));
+        assert!(!grid.insert(p(4, 4), North));
+        assert!(grid.insert(p(3, 4), South));
+        assert!(grid.contains(p(4, 4), West));
+        assert!(!grid.contains(p(4, 4), South));
+        assert_eq!(grid.directions(p(4, 4)), 0b1001);
+        assert_eq!(grid.count(), 3);
+        assert_eq!(grid.positions().count(), 2);
+        assert!(grid.remove(p(4, 4), North));
+        assert_eq!(grid.directions(p(4, 4)), 0b1000);
+        grid.clear();
+        assert!(grid.is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn diagonal_direction() {
+        DirectionBitGrid::new(2, 2).insert(p(0, 0), Direction::NorthEast);
+    }
+}
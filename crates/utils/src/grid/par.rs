@@ -0,0 +1,65 @@
+use rayon::prelude::*;
+
+use crate::position::Position;
+
+use super::Grid;
+
+/// Parallel traversal of grids, cells are spread over the rayon thread pool.
+impl<T: Sync> Grid<T> {
+    /// Cells with their position, in parallel.
+    pub fn par_positions(&self) -> impl IndexedParallelIterator<Item = (Position, &T)> {
+        self.data
+            .par_iter()
+            .enumerate()
+            .map(|(i, value)| (self.position(i), value))
+    }
+
+    /// Rows of the grid, in parallel.
+    pub fn par_rows(&self) -> impl IndexedParallelIterator<Item = &[T]> {
+        self.data.par_chunks_exact(self.width.max(1))
+    }
+
+    /// Parallel version of [`Grid::map`], rows are processed concurrently.
+    pub fn par_map<U, F>(&self, f: F) -> Grid<U>
+    where
+        U: Send,
+        F: Fn(&T) -> U + Sync + Send,
+    {
+        Grid {
+            width: self.width,
+            height: self.height,
+            data: self
+                .par_rows()
+                .flat_map_iter(|row| row.iter().map(&f))
+                .collect(),
+        }
+    }
+
+    /// Parallel version of [`Grid::count_where`], rows are processed concurrently.
+    pub fn par_count_where<P>(&self, predicate: P) -> usize
+    where
+        P: Fn(&T) -> bool + Sync + Send,
+    {
+        self.par_rows()
+            .map(|row| row.iter().filter(|v| predicate(v)).count())
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_sequential() {
+        let grid = Grid::from_fn(37, 23, |p| p.x * p.y % 7);
+        assert_eq!(grid.par_map(|v| v * 2), grid.map(|v| v * 2));
+        assert_eq!(
+            grid.par_count_where(|&v| v == 3),
+            grid.count_where(|&v| v == 3)
+        );
+        let positions: Vec<_> = grid.par_positions().collect();
+        assert_eq!(positions, grid.positions().collect::<Vec<_>>());
+        assert_eq!(grid.par_rows().count(), 23);
+    }
+}
@@ -0,0 +1,242 @@
+use std::{
+    collections::{hash_map, HashMap},
+    fmt::Display,
+    ops::Index,
+};
+
+use crate::point::Point;
+
+/// Unbounded grid storing only occupied cells.
+///
+/// The bounding box of the occupied cells is kept up to date on insertion and
+/// removal.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point, T>,
+    bounds: Option<(Point, Point)>,
+    /// Glyph displayed for empty cells.
+    pub background: char,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> SparseGrid<T> {
+    /// Create an empty grid, displaying empty cells as `.`.
+    pub fn new() -> Self {
+        Self::with_background('.')
+    }
+
+    /// Create an empty grid, displaying empty cells as `background`.
+    pub fn with_background(background: char) -> Self {
+        Self {
+            cells: HashMap::new(),
+            bounds: None,
+            background,
+        }
+    }
+
+    /// Create a grid from text, one row per line starting at the origin.
+    ///
+    /// `f` returns `None` for characters standing for empty cells.
+    pub fn parse<F>(input: &str, mut f: F) -> Self
+    where
+        F: FnMut(char) -> Option<T>,
+    {
+        let mut grid = Self::new();
+        for (y, line) in input.lines().enumerate() {
+            for (x, chr) in line.chars().enumerate() {
+                if let Some(value) = f(chr) {
+                    let point = Point {
+                        x: x as isize,
+                        y: y as isize,
+                    };
+                    grid.insert(point, value);
+                }
+            }
+        }
+        grid
+    }
+
+    /// Number of occupied cells.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Top-left and bottom-right occupied corners, both included.
+    pub fn bounds(&self) -> Option<(Point, Point)> {
+        self.bounds
+    }
+
+    pub fn contains(&self, point: &Point) -> bool {
+        self.cells.contains_key(point)
+    }
+
+    pub fn get(&self, point: &Point) -> Option<&T> {
+        self.cells.get(point)
+    }
+
+    pub fn get_mut(&mut self, point: &Point) -> Option<&mut T> {
+        self.cells.get_mut(point)
+    }
+
+    /// Set the value of a cell, returning the previous one.
+    pub fn insert(&mut self, point: Point, value: T) -> Option<T> {
+        self.bounds = Some(match self.bounds {
+            None => (point, point),
+            Some((min, max)) => (
+                Point {
+                    x: min.x.min(point.x),
+                    y: min.y.min(point.y),
+                },
+                Point {
+                    x: max.x.max(point.x),
+                    y: max.y.max(point.y),
+                },
+            ),
+        });
+        self.cells.insert(point, value)
+    }
+
+    /// Empty a cell, returning its value.
+    pub fn remove(&mut self, point: &Point) -> Option<T> {
+        let value = self.cells.remove(point)?;
+        if let Some((min, max)) = self.bounds {
+            if point.x == min.x || point.x == max.x || point.y == min.y || point.y == max.y {
+                self.bounds = self.compute_bounds();
+            }
+        }
+        Some(value)
+    }
+
+    /// Occupied cells, in no particular order.
+    pub fn iter(&self) -> hash_map::Iter<'_, Point, T> {
+        self.cells.iter()
+    }
+
+    fn compute_bounds(&self) -> Option<(Point, Point)> {
+        let mut points = self.cells.keys();
+        let first = *points.next()?;
+        Some(points.fold((first, first), |(min, max), p| {
+            (
+                Point {
+                    x: min.x.min(p.x),
+                    y: min.y.min(p.y),
+                },
+                Point {
+                    x: max.x.max(p.x),
+                    y: max.y.max(p.y),
+                },
+            )
+        }))
+    }
+}
+
+impl<T> Index<&Point> for SparseGrid<T> {
+    type Output = T;
+
+    fn index(&self, index: &Point) -> &Self::Output {
+        &self.cells[index]
+    }
+}
+
+impl<T> FromIterator<(Point, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Point, T)>>(iter: I) -> Self {
+        let mut grid = Self::new();
+        grid.extend(iter);
+        grid
+    }
+}
+
+impl<T> Extend<(Point, T)> for SparseGrid<T> {
+    fn extend<I: IntoIterator<Item = (Point, T)>>(&mut self, iter: I) {
+        for (point, value) in iter {
+            self.insert(point, value);
+        }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a SparseGrid<T> {
+    type Item = (&'a Point, &'a T);
+    type IntoIter = hash_map::Iter<'a, Point, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Display the bounding box of the occupied cells.
+impl<T> Display for SparseGrid<T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Some((min, max)) = self.bounds else {
+            return Ok(());
+        };
+        for y in min.y..=max.y {
+            for x in min.x..=max.x {
+                match self.cells.get(&Point { x, y }) {
+                    Some(value) => write!(f, "{}", value)?,
+                    None => write!(f, "{}", self.background)?,
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bounds() {
+        let mut grid = SparseGrid::new();
+        assert_eq!(grid.bounds(), None);
+        grid.insert(Point { x: 1, y: 1 }, 'a');
+        grid.insert(Point { x: -2, y: 3 }, 'b');
+        grid.insert(Point { x: 0, y: -1 }, 'c');
+        assert_eq!(
+            grid.bounds(),
+            Some((Point { x: -2, y: -1 }, Point { x: 1, y: 3 }))
+        );
+        assert_eq!(grid.remove(&Point { x: -2, y: 3 }), Some('b'));
+        assert_eq!(
+            grid.bounds(),
+            Some((Point { x: 0, y: -1 }, Point { x: 1, y: 1 }))
+        );
+        grid.remove(&Point { x: 1, y: 1 });
+        grid.remove(&Point { x: 0, y: -1 });
+        assert_eq!(grid.bounds(), None);
+        assert!(grid.is_empty());
+    }
+
+    #[test]
+    fn display() {
+        let grid: SparseGrid<char> = [(Point { x: -1, y: 0 }, '#'), (Point { x: 1, y: 1 }, '#')]
+            .into_iter()
+            .collect();
+        assert_eq!(grid.to_string(), "#..\n..#\n");
+    }
+
+    #[test]
+    fn parse() {
+        let grid = SparseGrid::parse("..a\n#..", |c| (c != '.').then_some(c));
+        assert_eq!(grid.len(), 2);
+        assert_eq!(grid[&Point { x: 2, y: 0 }], 'a');
+        assert_eq!(grid.get(&Point { x: 1, y: 0 }), None);
+        assert_eq!(
+            grid.bounds(),
+            Some((Point { x: 0, y: 0 }, Point { x: 2, y: 1 }))
+        );
+    }
+}
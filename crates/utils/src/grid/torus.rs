@@ -0,0 +1,216 @@
+use std::ops::{Index, IndexMut};
+
+use crate::{
+    movement::{Movement, CLOCKWISE},
+    position::Position,
+};
+
+use super::{Grid, PosIter};
+
+/// Grid whose opposite edges are joined: moving past an edge reenters the grid on
+/// the other side.
+///
+/// Positions used as indices are taken modulo the dimensions.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TorusGrid<T> {
+    grid: Grid<T>,
+}
+
+impl<T> TorusGrid<T> {
+    pub fn new(grid: Grid<T>) -> Self {
+        Self { grid }
+    }
+
+    /// Create a torus from its dimensions, with every cell set to `value`.
+    pub fn filled(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self::new(Grid::filled(width, height, value))
+    }
+
+    pub fn width(&self) -> usize {
+        self.grid.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.grid.height
+    }
+
+    pub fn as_grid(&self) -> &Grid<T> {
+        &self.grid
+    }
+
+    pub fn into_inner(self) -> Grid<T> {
+        self.grid
+    }
+
+    pub fn positions(&self) -> PosIter<'_, T> {
+        self.grid.positions()
+    }
+
+    /// Bring a position back inside the grid.
+    ///
+    /// # Panics
+    /// When the grid has no cell.
+    pub fn wrap(&self, position: Position) -> Position {
+        Position {
+            x: position.x % self.grid.width,
+            y: position.y % self.grid.height,
+        }
+    }
+
+    /// Apply `movement` to `position`, wrapping around the edges.
+    ///
+    /// There is no `Position + Movement` operator that wraps, this is the way to
+    /// move on a torus.
+    ///
+    /// # Panics
+    /// When the grid has no cell.
+    pub fn wrapping_move(&self, position: Position, movement: Movement) -> Position {
+        self.wrapping_move_n(position, movement, 1)
+    }
+
+    /// Apply `movement` to `position` `times` in a row, wrapping around the edges.
+    ///
+    /// Each component is reduced modulo the dimensions before being scaled, so
+    /// unlike `movement * times` this cannot overflow.
+    ///
+    /// # Panics
+    /// When the grid has no cell.
+    pub fn wrapping_move_n(
+        &self,
+        position: Position,
+        movement: Movement,
+        times: usize,
+    ) -> Position {
+        let reduce = |delta: isize, size: usize| {
+            let delta = delta.rem_euclid(size as isize) as u128;
+            (delta * (times % size) as u128 % size as u128) as usize
+        };
+        let position = self.wrap(position);
+        self.wrap(Position {
+            x: position.x + reduce(movement.x, self.grid.width),
+            y: position.y + reduce(movement.y, self.grid.height),
+        })
+    }
+
+    /// Same as [`TorusGrid::wrapping_move`], a torus has no edge to fall from.
+    pub fn checked_move(&self, position: Position, movement: Movement) -> Option<Position> {
+        Some(self.wrapping_move(position, movement))
+    }
+
+    /// Orthogonal neighbours of `position`, clockwise from up.
+    pub fn neighbors4(&self, position: Position) -> impl Iterator<Item = (Position, &T)> {
+        CLOCKWISE.iter().map(move |m| {
+            let neighbor = self.wrapping_move(position, *m);
+            (neighbor, &self[&neighbor])
+        })
+    }
+}
+
+impl TorusGrid<usize> {
+    /// Count how many of `positions` fall on each cell, after wrapping them.
+    pub fn occupancy<I>(width: usize, height: usize, positions: I) -> Self
+    where
+        I: IntoIterator<Item = Position>,
+    {
+        let mut torus = Self::filled(width, height, 0);
+        for position in positions {
+            torus[&position] += 1;
+        }
+        torus
+    }
+}
+
+impl<T> From<Grid<T>> for TorusGrid<T> {
+    fn from(grid: Grid<T>) -> Self {
+        Self::new(grid)
+    }
+}
+
+impl<T> Index<&Position> for TorusGrid<T> {
+    type Output = T;
+
+    fn index(&self, index: &Position) -> &Self::Output {
+        &self.grid[&self.wrap(*index)]
+    }
+}
+
+impl<T> IndexMut<&Position> for TorusGrid<T> {
+    fn index_mut(&mut self, index: &Position) -> &mut Self::Output {
+        let index = self.wrap(*index);
+        &mut self.grid[&index]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(0, 0, -1, 0 => (10, 0))]
+    #[test_case(10, 6, 1, 1 => (0, 0))]
+    #[test_case(2, 4, 2, -3 => (4, 1))]
+    #[test_case(2, 4, -300, 700 => (10, 4))]
+    fn wrapping_move(x: usize, y: usize, dx: isize, dy: isize) -> (usize, usize) {
+        let torus = TorusGrid::filled(11, 7, ());
+        let p = torus.wrapping_move(Position { x, y }, Movement { x: dx, y: dy });
+        (p.x, p.y)
+    }
+
+    #[test]
+    fn scaled_move() {
+        // Robot from the day 14 example, after 5 seconds.
+        let torus = TorusGrid::filled(11, 7, ());
+        let p = torus.wrapping_move(Position { x: 2, y: 4 }, Movement { x: 2, y: -3 } * 5);
+        assert_eq!(p, Position { x: 1, y: 3 });
+        let p = torus.wrapping_move_n(Position { x: 2, y: 4 }, Movement { x: 2, y: -3 }, 5);
+        assert_eq!(p, Position { x: 1, y: 3 });
+    }
+
+    #[test]
+    fn huge_scaled_move() {
+        // `Movement * usize::MAX` would overflow, usize::MAX = 4 mod 11 and 1 mod 7.
+        let torus = TorusGrid::filled(11, 7, ());
+        let movement = Movement {
+            x: isize::MIN,
+            y: isize::MAX,
+        };
+        let expected = torus.wrapping_move(
+            Position { x: 0, y: 0 },
+            Movement {
+                x: isize::MIN.rem_euclid(11) * 4,
+                y: isize::MAX.rem_euclid(7),
+            },
+        );
+        let p = torus.wrapping_move_n(Position { x: 0, y: 0 }, movement, usize::MAX);
+        assert_eq!(p, expected);
+    }
+
+    #[test]
+    #[should_panic]
+    fn empty_torus() {
+        TorusGrid::filled(0, 3, ()).wrap(Position { x: 1, y: 1 });
+    }
+
+    #[test]
+    fn neighbors() {
+        let torus = TorusGrid::filled(3, 2, ());
+        let found: Vec<_> = torus
+            .neighbors4(Position { x: 0, y: 0 })
+            .map(|(p, _)| (p.x, p.y))
+            .collect();
+        assert_eq!(found, [(0, 1), (1, 0), (0, 1), (2, 0)]);
+    }
+
+    #[test]
+    fn occupancy() {
+        let positions = [(0, 0), (3, 2), (1, 1), (4, 0)].map(|(x, y)| Position { x, y });
+        let torus = TorusGrid::occupancy(3, 2, positions);
+        assert_eq!(torus[&Position { x: 0, y: 0 }], 2);
+        assert_eq!(torus[&Position { x: 1, y: 0 }], 1);
+        assert_eq!(torus[&Position { x: 1, y: 1 }], 1);
+        assert_eq!(torus.positions().map(|(_, c)| c).sum::<usize>(), 4);
+    }
+}
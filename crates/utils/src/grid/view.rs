@@ -0,0 +1,177 @@
+use std::ops::Index;
+
+use crate::{movement::Movement, position::Position};
+
+use super::Grid;
+
+/// Borrowed rectangular view into a [`Grid`].
+///
+/// Positions given to a view are relative to its top-left corner.
+#[derive(Debug)]
+pub struct SubGrid<'a, T> {
+    grid: &'a Grid<T>,
+    origin: Position,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl<T> Clone for SubGrid<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for SubGrid<'_, T> {}
+
+impl<'a, T> SubGrid<'a, T> {
+    pub(super) fn new(grid: &'a Grid<T>, origin: Position, width: usize, height: usize) -> Self {
+        Self {
+            grid,
+            origin,
+            width,
+            height,
+        }
+    }
+
+    /// Position of the top-left corner of the view in the underlying grid.
+    pub fn origin(&self) -> Position {
+        self.origin
+    }
+
+    pub fn contains(&self, position: Position) -> bool {
+        position.x < self.width && position.y < self.height
+    }
+
+    pub fn get(&self, position: Position) -> Option<&'a T> {
+        self.contains(position)
+            .then(|| &self.grid[&self.absolute(position)])
+    }
+
+    /// Rows of the view, top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = &'a [T]> + '_ {
+        (0..self.height).map(|y| {
+            let start = (self.origin.y + y) * self.grid.width + self.origin.x;
+            &self.grid.data[start..start + self.width]
+        })
+    }
+
+    /// Cells of the view in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = &'a T> + '_ {
+        self.rows().flatten()
+    }
+
+    /// Cells of the view with their position relative to its top-left corner, in
+    /// row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = (Position, &'a T)> + '_ {
+        self.rows().enumerate().flat_map(|(y, row)| {
+            row.iter()
+                .enumerate()
+                .map(move |(x, value)| (Position { x, y }, value))
+        })
+    }
+
+    /// Copy the view into a grid of its own.
+    pub fn to_grid(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::from_fn(self.width, self.height, |p| self[&p].clone())
+    }
+
+    fn absolute(&self, position: Position) -> Position {
+        Position {
+            x: self.origin.x + position.x,
+            y: self.origin.y + position.y,
+        }
+    }
+}
+
+impl<T> Index<&Position> for SubGrid<'_, T> {
+    type Output = T;
+
+    fn index(&self, index: &Position) -> &Self::Output {
+        assert!(self.contains(*index), "position outside of the view");
+        &self.grid[&self.absolute(*index)]
+    }
+}
+
+/// Iterator over every `width` x `height` view of a grid, in row-major order of
+/// their top-left corner.
+pub struct Windows<'a, T> {
+    grid: &'a Grid<T>,
+    width: usize,
+    height: usize,
+    next: Option<Position>,
+}
+
+impl<'a, T> Windows<'a, T> {
+    pub(super) fn new(grid: &'a Grid<T>, width: usize, height: usize) -> Self {
+        let fits = width > 0 && height > 0 && width <= grid.width && height <= grid.height;
+        Self {
+            grid,
+            width,
+            height,
+            next: fits.then_some(Position { x: 0, y: 0 }),
+        }
+    }
+}
+
+impl<'a, T> Iterator for Windows<'a, T> {
+    type Item = SubGrid<'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let origin = self.next?;
+        self.next = if origin.x + self.width < self.grid.width {
+            Some(Position {
+                x: origin.x + 1,
+                y: origin.y,
+            })
+        } else if origin.y + self.height < self.grid.height {
+            Some(Position {
+                x: 0,
+                y: origin.y + 1,
+            })
+        } else {
+            None
+        };
+        Some(SubGrid::new(self.grid, origin, self.width, self.height))
+    }
+}
+
+/// Iterator over the cells met walking from a position in a fixed direction until
+/// the edge of the grid.
+pub struct Diagonal<'a, T> {
+    grid: &'a Grid<T>,
+    movement: Movement,
+    next: Option<Position>,
+}
+
+impl<'a, T> Diagonal<'a, T> {
+    /// Walk down and to the right.
+    pub(super) fn down_right(grid: &'a Grid<T>, start: Position) -> Self {
+        Self::new(grid, start, Movement { x: 1, y: 1 })
+    }
+
+    /// Walk down and to the left.
+    pub(super) fn down_left(grid: &'a Grid<T>, start: Position) -> Self {
+        Self::new(grid, start, Movement { x: -1, y: 1 })
+    }
+
+    fn new(grid: &'a Grid<T>, start: Position, movement: Movement) -> Self {
+        Self {
+            grid,
+            movement,
+            next: grid.contains(start).then_some(start),
+        }
+    }
+}
+
+impl<'a, T> Iterator for Diagonal<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.next?;
+        self.next = self.grid.checked_move(current, self.movement);
+        Some(&self.grid[&current])
+    }
+}
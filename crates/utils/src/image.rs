@@ -0,0 +1,99 @@
+use std::io::{self, Write};
+
+use crate::grid::Grid;
+
+/// Red, green and blue components of a pixel.
+pub type Rgb = [u8; 3];
+
+/// Export of grids as images, one pixel per cell.
+impl<T> Grid<T> {
+    /// Write a binary PBM (black and white) image, cells for which `is_set` holds
+    /// are black.
+    pub fn write_pbm<W, F>(&self, mut out: W, mut is_set: F) -> io::Result<()>
+    where
+        W: Write,
+        F: FnMut(&T) -> bool,
+    {
+        writeln!(out, "P4\n{} {}", self.width, self.height)?;
+        let mut line = vec![0u8; self.width.div_ceil(8)];
+        for row in self.rows() {
+            line.fill(0);
+            for (x, cell) in row.iter().enumerate() {
+                if is_set(cell) {
+                    line[x / 8] |= 0x80 >> (x % 8);
+                }
+            }
+            out.write_all(&line)?;
+        }
+        out.flush()
+    }
+
+    /// Write a binary PPM (colour) image, using `colour` to paint each cell.
+    pub fn write_ppm<W, F>(&self, mut out: W, colour: F) -> io::Result<()>
+    where
+        W: Write,
+        F: FnMut(&T) -> Rgb,
+    {
+        writeln!(out, "P6\n{} {}\n255", self.width, self.height)?;
+        out.write_all(&self.pixels(colour))?;
+        out.flush()
+    }
+
+    /// Write a PNG image, using `colour` to paint each cell.
+    #[cfg(feature = "png")]
+    pub fn write_png<W, F>(&self, out: W, colour: F) -> Result<(), png::EncodingError>
+    where
+        W: Write,
+        F: FnMut(&T) -> Rgb,
+    {
+        let mut encoder = png::Encoder::new(out, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&self.pixels(colour))?;
+        writer.finish()
+    }
+
+    fn pixels<F>(&self, mut colour: F) -> Vec<u8>
+    where
+        F: FnMut(&T) -> Rgb,
+    {
+        self.rows().flatten().flat_map(&mut colour).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::grid::Grid;
+
+    fn grid() -> Grid<char> {
+        Grid::parse("#........#\n.#........", Ok::<_, ()>).unwrap()
+    }
+
+    #[test]
+    fn pbm() {
+        let mut out = Vec::new();
+        grid().write_pbm(&mut out, |&c| c == '#').unwrap();
+        assert_eq!(out, b"P4\n10 2\n\x80\x40\x40\x00");
+    }
+
+    #[test]
+    fn ppm() {
+        let grid = Grid::parse("ab", Ok::<_, ()>).unwrap();
+        let mut out = Vec::new();
+        grid.write_ppm(
+            &mut out,
+            |&c| if c == 'a' { [255, 0, 0] } else { [0, 0, 255] },
+        )
+        .unwrap();
+        assert_eq!(out, b"P6\n2 1\n255\n\xff\x00\x00\x00\x00\xff");
+    }
+
+    #[cfg(feature = "png")]
+    #[test]
+    fn png() {
+        let mut out = Vec::new();
+        grid().write_png(&mut out, |&c| [c as u8; 3]).unwrap();
+        assert!(out.starts_with(b"\x89PNG\r\n\x1a\n"));
+    }
+}
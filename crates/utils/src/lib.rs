@@ -1,4 +1,16 @@
+pub mod components;
+pub mod disjoint_set;
 pub mod grid;
+pub mod image;
+pub mod line;
 pub mod math;
 pub mod movement;
+pub mod nd;
+pub mod parse;
+pub mod pattern;
+pub mod point;
 pub mod position;
+pub mod rect;
+pub mod region;
+pub mod render;
+pub mod search;
@@ -0,0 +1,153 @@
+use crate::{movement::Movement, position::Position};
+
+/// Every lattice point on the line through two positions, in order.
+///
+/// Starts at the point closest to the edge of the grid behind the line and
+/// may never end, so bound it with `take_while` or similar.
+#[derive(Clone, Debug)]
+pub struct Line {
+    next: Option<Position>,
+    step: Movement,
+}
+
+impl Line {
+    /// Line through `a` and `b`, `None` when they are the same position.
+    pub fn through(a: Position, b: Position) -> Option<Self> {
+        let mut step = Movement::between(a, b)?.primitive();
+        if step.x < 0 || (step.x == 0 && step.y < 0) {
+            step = step * -1;
+        }
+        // Stepping backwards lowers a component with a positive step, so it
+        // runs out somewhere.
+        let back = [(a.x, step.x), (a.y, step.y)]
+            .into_iter()
+            .filter(|&(_, s)| s > 0)
+            .map(|(c, s)| c / s as usize)
+            .min()?;
+        Some(Self {
+            next: a.checked_move(step * -(back as isize)),
+            step,
+        })
+    }
+
+    /// Primitive step between consecutive points.
+    pub fn step(&self) -> Movement {
+        self.step
+    }
+}
+
+impl Iterator for Line {
+    type Item = Position;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.next?;
+        self.next = current.checked_move(self.step);
+        Some(current)
+    }
+}
+
+/// Cells covered by the segment between two positions, both ends included,
+/// using Bresenham's algorithm.
+#[derive(Clone, Debug)]
+pub struct Segment {
+    current: Option<(isize, isize)>,
+    end: (isize, isize),
+    dx: isize,
+    dy: isize,
+    sx: isize,
+    sy: isize,
+    err: isize,
+}
+
+impl Segment {
+    pub fn new(from: Position, to: Position) -> Self {
+        let start = (from.x as isize, from.y as isize);
+        let end = (to.x as isize, to.y as isize);
+        let dx = (end.0 - start.0).abs();
+        let dy = -(end.1 - start.1).abs();
+        Self {
+            current: Some(start),
+            end,
+            dx,
+            dy,
+            sx: (end.0 - start.0).signum(),
+            sy: (end.1 - start.1).signum(),
+            err: dx + dy,
+        }
+    }
+}
+
+impl Iterator for Segment {
+    type Item = Position;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (mut x, mut y) = self.current?;
+        let out = Position {
+            x: x as usize,
+            y: y as usize,
+        };
+        if (x, y) == self.end {
+            self.current = None;
+        } else {
+            let e2 = 2 * self.err;
+            if e2 >= self.dy {
+                self.err += self.dy;
+                x += self.sx;
+            }
+            if e2 <= self.dx {
+                self.err += self.dx;
+                y += self.sy;
+            }
+            self.current = Some((x, y));
+        }
+        Some(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn positions(points: &[(usize, usize)]) -> Vec<Position> {
+        points.iter().map(|&(x, y)| Position { x, y }).collect()
+    }
+
+    #[test]
+    fn line_through() {
+        let line = Line::through(Position { x: 4, y: 2 }, Position { x: 2, y: 3 }).unwrap();
+        assert_eq!(line.step(), Movement { x: 2, y: -1 });
+        assert_eq!(
+            line.collect::<Vec<_>>(),
+            positions(&[(0, 4), (2, 3), (4, 2), (6, 1), (8, 0)])
+        );
+
+        let line = Line::through(Position { x: 1, y: 1 }, Position { x: 3, y: 5 }).unwrap();
+        assert_eq!(
+            line.take(3).collect::<Vec<_>>(),
+            positions(&[(1, 1), (2, 3), (3, 5)])
+        );
+
+        let line = Line::through(Position { x: 3, y: 6 }, Position { x: 3, y: 2 }).unwrap();
+        assert_eq!(
+            line.take(3).collect::<Vec<_>>(),
+            positions(&[(3, 0), (3, 1), (3, 2)])
+        );
+
+        let p = Position { x: 5, y: 5 };
+        assert!(Line::through(p, p).is_none());
+    }
+
+    #[test]
+    fn segment() {
+        let from = Position { x: 0, y: 1 };
+        let to = Position { x: 6, y: 4 };
+        let expected = positions(&[(0, 1), (1, 2), (2, 2), (3, 3), (4, 3), (5, 4), (6, 4)]);
+        assert_eq!(Segment::new(from, to).collect::<Vec<_>>(), expected);
+        assert_eq!(Segment::new(to, from).count(), expected.len());
+        assert_eq!(Segment::new(from, from).collect::<Vec<_>>(), vec![from]);
+        assert_eq!(
+            Segment::new(Position { x: 3, y: 3 }, Position { x: 0, y: 0 }).collect::<Vec<_>>(),
+            positions(&[(3, 3), (2, 2), (1, 1), (0, 0)])
+        );
+    }
+}
@@ -1,19 +1,144 @@
-use std::arch::asm;
-
-/// Quotient and remainder in a single operation without 0 division check.
-/// # Safety
-/// UB when b == 0
-pub unsafe fn asm_div_rem(a: u64, b: u64) -> (u64, u64) {
-    let mut tmp: u64 = a;
-    let mut remainder: u64 = 0;
-    unsafe {
-        asm!(
-            "div {divisor}",
-            inout("eax") tmp,
-            inout("edx") remainder,
-            divisor = in(reg) b,
-            options(pure, nomem, nostack),
-        );
-    }
-    (tmp, remainder)
+/// Quotient and remainder in a single operation, `None` when `b == 0`.
+#[inline]
+pub fn div_rem(a: u64, b: u64) -> Option<(u64, u64)> {
+    // Both operators compile to a single `div` on x86_64.
+    Some((a.checked_div(b)?, a % b))
+}
+
+/// Division by a fixed divisor, replaced with a multiplication by its
+/// precomputed reciprocal.
+///
+/// Worth it when the same divisor is used many times and is not known at
+/// compile time, such as the powers of ten splitting numbers by digits.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Divider {
+    divisor: u64,
+    /// `ceil(2^128 / divisor)`, wrapped to 0 when dividing by 1.
+    reciprocal: u128,
+}
+
+/// Every power of ten that fits in a `u64`.
+const POWERS_OF_TEN: [Divider; 20] = {
+    let mut out = [Divider::unchecked(1); 20];
+    let mut i = 1;
+    while i < out.len() {
+        out[i] = Divider::unchecked(out[i - 1].divisor * 10);
+        i += 1;
+    }
+    out
+};
+
+impl Divider {
+    /// `None` when `divisor == 0`.
+    pub const fn new(divisor: u64) -> Option<Self> {
+        if divisor == 0 {
+            None
+        } else {
+            Some(Self::unchecked(divisor))
+        }
+    }
+
+    const fn unchecked(divisor: u64) -> Self {
+        Self {
+            divisor,
+            reciprocal: (u128::MAX / divisor as u128).wrapping_add(1),
+        }
+    }
+
+    /// Divider by `10^exp`.
+    ///
+    /// # Panics
+    /// When `exp > 19`, the result would not fit in a `u64`.
+    pub fn power_of_ten(exp: u32) -> Self {
+        POWERS_OF_TEN[exp as usize]
+    }
+
+    pub fn divisor(&self) -> u64 {
+        self.divisor
+    }
+
+    #[inline]
+    pub fn div(&self, a: u64) -> u64 {
+        if self.reciprocal == 0 {
+            return a;
+        }
+        // High 128 bits of the 192 bits product `reciprocal * a`.
+        let low = (self.reciprocal as u64 as u128 * a as u128) >> 64;
+        let high = (self.reciprocal >> 64) * a as u128;
+        ((high + low) >> 64) as u64
+    }
+
+    #[inline]
+    pub fn div_rem(&self, a: u64) -> (u64, u64) {
+        let quotient = self.div(a);
+        (quotient, a - quotient * self.divisor)
+    }
+}
+
+/// Greatest common divisor, `gcd(0, 0) == 0`.
+pub fn gcd(mut a: usize, mut b: usize) -> usize {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(17, 5 => Some((3, 2)))]
+    #[test_case(u64::MAX, 1 => Some((u64::MAX, 0)))]
+    #[test_case(0, 9 => Some((0, 0)))]
+    #[test_case(4, 0 => None)]
+    fn safe_div_rem(a: u64, b: u64) -> Option<(u64, u64)> {
+        div_rem(a, b)
+    }
+
+    #[test]
+    fn divider() {
+        let numerators = [0, 1, 9, 10, 99, 12345, 1 << 63, u64::MAX - 1, u64::MAX];
+        let divisors = [
+            1,
+            2,
+            3,
+            7,
+            10,
+            641,
+            1 << 32,
+            1 << 63,
+            u64::MAX - 1,
+            u64::MAX,
+        ];
+        for d in divisors {
+            let divider = Divider::new(d).unwrap();
+            for a in numerators
+                .into_iter()
+                .chain((0..1000u64).map(|i| i.wrapping_mul(0x9e37_79b9_7f4a_7c15)))
+            {
+                assert_eq!(divider.div_rem(a), (a / d, a % d), "{a} / {d}");
+            }
+        }
+        assert_eq!(Divider::new(0), None);
+    }
+
+    #[test]
+    fn powers_of_ten() {
+        for exp in 0..20 {
+            let divider = Divider::power_of_ten(exp);
+            assert_eq!(divider.divisor(), 10u64.pow(exp));
+            assert_eq!(
+                divider.div_rem(253_000_017),
+                (253_000_017 / 10u64.pow(exp), 253_000_017 % 10u64.pow(exp))
+            );
+        }
+    }
+
+    #[test_case(12, 18 => 6)]
+    #[test_case(0, 5 => 5)]
+    #[test_case(0, 0 => 0)]
+    fn gcd_cases(a: usize, b: usize) -> usize {
+        gcd(a, b)
+    }
 }
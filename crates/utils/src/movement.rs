@@ -1,4 +1,10 @@
-use crate::position::Position;
+use std::{ops::Mul, str::FromStr};
+
+use nom::{combinator::all_consuming, error::Error, Finish};
+
+use crate::{math::gcd, parse, position::Position};
+
+pub mod hex;
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Movement {
@@ -13,6 +19,49 @@ impl Movement {
             y: isize::try_from(p2.y).ok()? - isize::try_from(p1.y).ok()?,
         })
     }
+
+    /// Smallest step in the same direction, landing on every lattice point
+    /// along the way. The zero movement stays zero.
+    pub fn primitive(self) -> Self {
+        match gcd(self.x.unsigned_abs(), self.y.unsigned_abs()) {
+            0 => self,
+            g => Self {
+                x: self.x / g as isize,
+                y: self.y / g as isize,
+            },
+        }
+    }
+
+    /// Multiply both components by `k`, `None` on overflow.
+    pub fn scale(self, k: isize) -> Option<Self> {
+        Some(Self {
+            x: self.x.checked_mul(k)?,
+            y: self.y.checked_mul(k)?,
+        })
+    }
+}
+
+impl Mul<isize> for Movement {
+    type Output = Movement;
+
+    fn mul(self, rhs: isize) -> Self::Output {
+        Movement {
+            x: self.x * rhs,
+            y: self.y * rhs,
+        }
+    }
+}
+
+/// Parse `x,y`.
+impl FromStr for Movement {
+    type Err = Error<String>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        all_consuming(parse::movement("", ","))(s)
+            .finish()
+            .map(|(_, v)| v)
+            .map_err(|e| Error::new(e.input.to_owned(), e.code))
+    }
 }
 
 pub const UP: Movement = Movement { x: 0, y: -1 };
@@ -20,4 +69,242 @@ pub const RIGHT: Movement = Movement { x: 1, y: 0 };
 pub const DOWN: Movement = Movement { x: 0, y: 1 };
 pub const LEFT: Movement = Movement { x: -1, y: 0 };
 
+pub const UP_RIGHT: Movement = Movement { x: 1, y: -1 };
+pub const DOWN_RIGHT: Movement = Movement { x: 1, y: 1 };
+pub const DOWN_LEFT: Movement = Movement { x: -1, y: 1 };
+pub const UP_LEFT: Movement = Movement { x: -1, y: -1 };
+
 pub const CLOCKWISE: [Movement; 4] = [UP, RIGHT, DOWN, LEFT];
+
+/// Diagonal movements, clockwise from up-right.
+pub const DIAGONALS: [Movement; 4] = [UP_RIGHT, DOWN_RIGHT, DOWN_LEFT, UP_LEFT];
+
+/// Every movement to an adjacent cell, clockwise from up.
+pub const ALL8: [Movement; 8] = [
+    UP, UP_RIGHT, RIGHT, DOWN_RIGHT, DOWN, DOWN_LEFT, LEFT, UP_LEFT,
+];
+
+/// Chess knight jumps, clockwise from two up and one right.
+pub const KNIGHT: [Movement; 8] = [
+    Movement { x: 1, y: -2 },
+    Movement { x: 2, y: -1 },
+    Movement { x: 2, y: 1 },
+    Movement { x: 1, y: 2 },
+    Movement { x: -1, y: 2 },
+    Movement { x: -2, y: 1 },
+    Movement { x: -2, y: -1 },
+    Movement { x: -1, y: -2 },
+];
+
+/// Compass direction, with up as north.
+///
+/// Orthogonal directions come first, so that their index matches [`CLOCKWISE`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+    NorthEast,
+    SouthEast,
+    SouthWest,
+    NorthWest,
+}
+
+impl Direction {
+    /// Orthogonal directions, clockwise from north.
+    pub const ORTHOGONAL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    /// Diagonal directions, clockwise from north-east.
+    pub const DIAGONAL: [Direction; 4] = [
+        Direction::NorthEast,
+        Direction::SouthEast,
+        Direction::SouthWest,
+        Direction::NorthWest,
+    ];
+
+    /// Every direction, in index order.
+    pub const ALL: [Direction; 8] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+        Direction::NorthEast,
+        Direction::SouthEast,
+        Direction::SouthWest,
+        Direction::NorthWest,
+    ];
+
+    /// Index in [`Direction::ALL`], below 4 for orthogonal directions.
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    /// Single bit set at [`Direction::index`], to store directions in a mask.
+    pub fn bit(self) -> u8 {
+        1 << self.index()
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        self.index() >= 4
+    }
+
+    /// Quarter turn clockwise.
+    pub fn turn_right(self) -> Self {
+        let i = self.index();
+        Self::ALL[i & 4 | (i + 1) & 3]
+    }
+
+    /// Quarter turn counter-clockwise.
+    pub fn turn_left(self) -> Self {
+        let i = self.index();
+        Self::ALL[i & 4 | (i + 3) & 3]
+    }
+
+    pub fn opposite(self) -> Self {
+        let i = self.index();
+        Self::ALL[i & 4 | (i + 2) & 3]
+    }
+
+    pub fn movement(self) -> Movement {
+        match self {
+            Direction::North => UP,
+            Direction::East => RIGHT,
+            Direction::South => DOWN,
+            Direction::West => LEFT,
+            Direction::NorthEast => UP_RIGHT,
+            Direction::SouthEast => DOWN_RIGHT,
+            Direction::SouthWest => DOWN_LEFT,
+            Direction::NorthWest => UP_LEFT,
+        }
+    }
+}
+
+impl From<Direction> for Movement {
+    fn from(value: Direction) -> Self {
+        value.movement()
+    }
+}
+
+/// Only unit movements have a direction.
+impl TryFrom<Movement> for Direction {
+    type Error = Movement;
+
+    fn try_from(value: Movement) -> Result<Self, Self::Error> {
+        Direction::ALL
+            .into_iter()
+            .find(|d| d.movement() == value)
+            .ok_or(value)
+    }
+}
+
+/// Parse arrows (`^>v<`), compass points (`NESW`) or sides (`UDLR`).
+impl TryFrom<char> for Direction {
+    type Error = char;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '^' | 'N' | 'U' => Ok(Direction::North),
+            '>' | 'E' | 'R' => Ok(Direction::East),
+            'v' | 'S' | 'D' => Ok(Direction::South),
+            '<' | 'W' | 'L' => Ok(Direction::West),
+            other => Err(other),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(Direction::North => (Direction::East, Direction::West, Direction::South))]
+    #[test_case(Direction::West => (Direction::North, Direction::South, Direction::East))]
+    #[test_case(Direction::NorthEast => (Direction::SouthEast, Direction::NorthWest, Direction::SouthWest))]
+    #[test_case(Direction::NorthWest => (Direction::NorthEast, Direction::SouthWest, Direction::SouthEast))]
+    fn turns(direction: Direction) -> (Direction, Direction, Direction) {
+        (
+            direction.turn_right(),
+            direction.turn_left(),
+            direction.opposite(),
+        )
+    }
+
+    #[test]
+    fn matches_clockwise() {
+        for (direction, movement) in Direction::ORTHOGONAL.into_iter().zip(CLOCKWISE) {
+            assert_eq!(Movement::from(direction), movement);
+            assert_eq!(CLOCKWISE[direction.index()], movement);
+        }
+        for direction in Direction::ALL {
+            assert_eq!(Direction::try_from(direction.movement()), Ok(direction));
+            assert_eq!(direction.turn_right().turn_left(), direction);
+        }
+        let mask = Direction::ALL.iter().fold(0, |mask, d| mask | d.bit());
+        assert_eq!(mask, 0xff);
+    }
+
+    #[test]
+    fn rotational_order() {
+        for (i, movement) in ALL8.into_iter().enumerate() {
+            assert_eq!(
+                movement,
+                if i % 2 == 0 {
+                    CLOCKWISE[i / 2]
+                } else {
+                    DIAGONALS[i / 2]
+                }
+            );
+        }
+        for direction in Direction::DIAGONAL {
+            assert_eq!(direction.movement(), DIAGONALS[direction.index() - 4]);
+        }
+        for (i, knight) in KNIGHT.into_iter().enumerate() {
+            assert_eq!(knight.x.abs() + knight.y.abs(), 3);
+            assert!(!KNIGHT[i + 1..].contains(&knight));
+        }
+    }
+
+    #[test_case(Movement { x: 4, y: -6 } => Movement { x: 2, y: -3 })]
+    #[test_case(Movement { x: 0, y: 5 } => Movement { x: 0, y: 1 })]
+    #[test_case(Movement { x: -3, y: 7 } => Movement { x: -3, y: 7 })]
+    #[test_case(Movement { x: 0, y: 0 } => Movement { x: 0, y: 0 })]
+    fn primitive(movement: Movement) -> Movement {
+        movement.primitive()
+    }
+
+    #[test_case("-3,7" => Ok(Movement { x: -3, y: 7 }))]
+    #[test_case("+1,0" => Ok(Movement { x: 1, y: 0 }))]
+    #[test_case("1;0" => Err(()))]
+    fn from_str(s: &str) -> Result<Movement, ()> {
+        s.parse().map_err(|_| ())
+    }
+
+    #[test]
+    fn scale() {
+        assert_eq!(UP_RIGHT.scale(3), Some(Movement { x: 3, y: -3 }));
+        assert_eq!(
+            RIGHT.scale(isize::MAX),
+            Some(Movement {
+                x: isize::MAX,
+                y: 0
+            })
+        );
+        assert_eq!(UP_LEFT.scale(isize::MIN), None);
+    }
+
+    #[test_case('^' => Ok(Direction::North))]
+    #[test_case('>' => Ok(Direction::East))]
+    #[test_case('v' => Ok(Direction::South))]
+    #[test_case('L' => Ok(Direction::West))]
+    #[test_case('S' => Ok(Direction::South))]
+    #[test_case('x' => Err('x'))]
+    fn parse(c: char) -> Result<Direction, char> {
+        Direction::try_from(c)
+    }
+}
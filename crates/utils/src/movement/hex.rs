@@ -0,0 +1,134 @@
+use std::ops::{Add, Mul, Sub};
+
+/// Cell of a hexagonal grid in axial coordinates, pointy side up.
+///
+/// The third cube coordinate is implied, `q + r + s == 0`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Hex {
+    pub q: isize,
+    pub r: isize,
+}
+
+pub const EAST: Hex = Hex { q: 1, r: 0 };
+pub const NORTH_EAST: Hex = Hex { q: 1, r: -1 };
+pub const NORTH_WEST: Hex = Hex { q: 0, r: -1 };
+pub const WEST: Hex = Hex { q: -1, r: 0 };
+pub const SOUTH_WEST: Hex = Hex { q: -1, r: 1 };
+pub const SOUTH_EAST: Hex = Hex { q: 0, r: 1 };
+
+/// The six steps to adjacent cells, counter-clockwise from east.
+pub const DIRECTIONS: [Hex; 6] = [EAST, NORTH_EAST, NORTH_WEST, WEST, SOUTH_WEST, SOUTH_EAST];
+
+impl Hex {
+    /// Create a cell from cube coordinates, `None` if they do not sum to zero.
+    pub fn from_cube(q: isize, r: isize, s: isize) -> Option<Self> {
+        (q + r + s == 0).then_some(Self { q, r })
+    }
+
+    /// Third cube coordinate.
+    pub fn s(&self) -> isize {
+        -self.q - self.r
+    }
+
+    /// The six adjacent cells, counter-clockwise from east.
+    pub fn neighbors(self) -> impl Iterator<Item = Hex> {
+        DIRECTIONS.into_iter().map(move |d| self + d)
+    }
+
+    /// Number of steps between two cells.
+    pub fn distance(self, other: Hex) -> usize {
+        let d = self - other;
+        (d.q.unsigned_abs() + d.r.unsigned_abs() + d.s().unsigned_abs()) / 2
+    }
+
+    /// Cells exactly `radius` steps away, starting east and going
+    /// counter-clockwise.
+    pub fn ring(self, radius: usize) -> Vec<Hex> {
+        if radius == 0 {
+            return vec![self];
+        }
+        let mut out = Vec::with_capacity(6 * radius);
+        let mut current = self + EAST * radius as isize;
+        for direction in [NORTH_WEST, WEST, SOUTH_WEST, SOUTH_EAST, EAST, NORTH_EAST] {
+            for _ in 0..radius {
+                out.push(current);
+                current = current + direction;
+            }
+        }
+        out
+    }
+}
+
+impl Add for Hex {
+    type Output = Hex;
+
+    fn add(self, rhs: Hex) -> Self::Output {
+        Hex {
+            q: self.q + rhs.q,
+            r: self.r + rhs.r,
+        }
+    }
+}
+
+impl Sub for Hex {
+    type Output = Hex;
+
+    fn sub(self, rhs: Hex) -> Self::Output {
+        Hex {
+            q: self.q - rhs.q,
+            r: self.r - rhs.r,
+        }
+    }
+}
+
+impl Mul<isize> for Hex {
+    type Output = Hex;
+
+    fn mul(self, rhs: isize) -> Self::Output {
+        Hex {
+            q: self.q * rhs,
+            r: self.r * rhs,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    const ORIGIN: Hex = Hex { q: 0, r: 0 };
+
+    #[test_case(Hex { q: 3, r: -3 } => 3)]
+    #[test_case(Hex { q: -2, r: -1 } => 3)]
+    #[test_case(Hex { q: 1, r: 2 } => 3)]
+    #[test_case(ORIGIN => 0)]
+    fn distance(hex: Hex) -> usize {
+        ORIGIN.distance(hex)
+    }
+
+    #[test]
+    fn neighbors() {
+        let center = Hex { q: 2, r: -1 };
+        for neighbor in center.neighbors() {
+            assert_eq!(center.distance(neighbor), 1);
+        }
+        assert_eq!(center.neighbors().count(), 6);
+        assert_eq!(Hex::from_cube(1, -2, 1), Some(Hex { q: 1, r: -2 }));
+        assert_eq!(Hex::from_cube(1, 1, 1), None);
+    }
+
+    #[test_case(0 => 1)]
+    #[test_case(1 => 6)]
+    #[test_case(3 => 18)]
+    fn ring(radius: usize) -> usize {
+        let center = Hex { q: -1, r: 4 };
+        let ring = center.ring(radius);
+        assert!(ring.iter().all(|&h| center.distance(h) == radius));
+        let mut unique = ring.clone();
+        unique.sort();
+        unique.dedup();
+        assert_eq!(unique.len(), ring.len());
+        ring.len()
+    }
+}
@@ -0,0 +1,262 @@
+use std::ops::{Index, IndexMut};
+
+use crate::{movement::Movement, position::Position};
+
+/// Position in a `D`-dimensional grid, first axis first.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PositionN<const D: usize>(pub [usize; D]);
+
+/// Displacement in a `D`-dimensional grid, first axis first.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MovementN<const D: usize>(pub [isize; D]);
+
+pub type Position3 = PositionN<3>;
+pub type Movement3 = MovementN<3>;
+pub type Grid3<T> = GridN<T, 3>;
+
+impl<const D: usize> PositionN<D> {
+    pub fn checked_move(&self, movement: MovementN<D>) -> Option<Self> {
+        let mut out = self.0;
+        for (coordinate, delta) in out.iter_mut().zip(movement.0) {
+            *coordinate = coordinate.checked_add_signed(delta)?;
+        }
+        Some(Self(out))
+    }
+}
+
+impl<const D: usize> MovementN<D> {
+    /// One step along a single axis, both ways: 6 movements in 3D.
+    pub fn orthogonal() -> impl Iterator<Item = Self> {
+        (0..D).flat_map(|axis| {
+            [-1, 1].map(|step| {
+                let mut delta = [0; D];
+                delta[axis] = step;
+                Self(delta)
+            })
+        })
+    }
+
+    /// Every step to a cell touching by a face, an edge or a corner: 26 movements
+    /// in 3D.
+    pub fn all_around() -> impl Iterator<Item = Self> {
+        (0..3usize.pow(D as u32))
+            .map(|mut code| {
+                let mut delta = [0; D];
+                for d in delta.iter_mut() {
+                    *d = (code % 3) as isize - 1;
+                    code /= 3;
+                }
+                Self(delta)
+            })
+            .filter(|m| m.0.iter().any(|&d| d != 0))
+    }
+}
+
+impl From<Position> for PositionN<2> {
+    fn from(value: Position) -> Self {
+        Self([value.x, value.y])
+    }
+}
+
+impl From<PositionN<2>> for Position {
+    fn from(value: PositionN<2>) -> Self {
+        let [x, y] = value.0;
+        Position { x, y }
+    }
+}
+
+impl From<Movement> for MovementN<2> {
+    fn from(value: Movement) -> Self {
+        Self([value.x, value.y])
+    }
+}
+
+impl From<MovementN<2>> for Movement {
+    fn from(value: MovementN<2>) -> Self {
+        let [x, y] = value.0;
+        Movement { x, y }
+    }
+}
+
+/// Dense `D`-dimensional grid, the first axis varying fastest in memory.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct GridN<T, const D: usize> {
+    pub dims: [usize; D],
+    data: Vec<T>,
+}
+
+impl<T, const D: usize> GridN<T, D> {
+    /// Create a grid from its dimensions, with every cell set to `value`.
+    pub fn filled(dims: [usize; D], value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            dims,
+            data: vec![value; dims.iter().product()],
+        }
+    }
+
+    /// Create a grid from its dimensions, computing each cell from its position.
+    pub fn from_fn<F>(dims: [usize; D], f: F) -> Self
+    where
+        F: FnMut(PositionN<D>) -> T,
+    {
+        let len = dims.iter().product();
+        let data = (0..len)
+            .map(|i| Self::position_of(dims, i))
+            .map(f)
+            .collect();
+        Self { dims, data }
+    }
+
+    fn position_of(dims: [usize; D], mut index: usize) -> PositionN<D> {
+        let mut out = [0; D];
+        for (coordinate, size) in out.iter_mut().zip(dims) {
+            *coordinate = index % size;
+            index /= size;
+        }
+        PositionN(out)
+    }
+
+    fn index_of(&self, position: &PositionN<D>) -> usize {
+        assert!(self.contains(position), "position outside of the grid");
+        position
+            .0
+            .iter()
+            .zip(self.dims)
+            .rev()
+            .fold(0, |acc, (coordinate, size)| acc * size + coordinate)
+    }
+
+    pub fn contains(&self, position: &PositionN<D>) -> bool {
+        position.0.iter().zip(self.dims).all(|(c, size)| *c < size)
+    }
+
+    pub fn get(&self, position: &PositionN<D>) -> Option<&T> {
+        self.contains(position).then(|| &self[position])
+    }
+
+    pub fn checked_move(
+        &self,
+        position: PositionN<D>,
+        movement: MovementN<D>,
+    ) -> Option<PositionN<D>> {
+        position.checked_move(movement).filter(|n| self.contains(n))
+    }
+
+    /// Cells with their position, first axis varying fastest.
+    pub fn positions(&self) -> impl Iterator<Item = (PositionN<D>, &T)> {
+        self.data
+            .iter()
+            .enumerate()
+            .map(|(i, value)| (Self::position_of(self.dims, i), value))
+    }
+
+    /// Neighbours sharing a face with `position`: 6 in 3D.
+    pub fn neighbors_orthogonal(
+        &self,
+        position: PositionN<D>,
+    ) -> impl Iterator<Item = (PositionN<D>, &T)> {
+        MovementN::orthogonal()
+            .filter_map(move |m| self.checked_move(position, m))
+            .map(|n| (n, &self[&n]))
+    }
+
+    /// Neighbours sharing a face, an edge or a corner with `position`: 26 in 3D.
+    pub fn neighbors_all(
+        &self,
+        position: PositionN<D>,
+    ) -> impl Iterator<Item = (PositionN<D>, &T)> {
+        MovementN::all_around()
+            .filter_map(move |m| self.checked_move(position, m))
+            .map(|n| (n, &self[&n]))
+    }
+}
+
+impl<T, const D: usize> Index<&PositionN<D>> for GridN<T, D> {
+    type Output = T;
+
+    fn index(&self, index: &PositionN<D>) -> &Self::Output {
+        &self.data[self.index_of(index)]
+    }
+}
+
+impl<T, const D: usize> IndexMut<&PositionN<D>> for GridN<T, D> {
+    fn index_mut(&mut self, index: &PositionN<D>) -> &mut Self::Output {
+        let index = self.index_of(index);
+        &mut self.data[index]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn movements() {
+        assert_eq!(Movement3::orthogonal().count(), 6);
+        assert_eq!(Movement3::all_around().count(), 26);
+        assert_eq!(MovementN::<2>::all_around().count(), 8);
+        assert!(Movement3::orthogonal().all(|m| m.0.iter().map(|d| d.abs()).sum::<isize>() == 1));
+    }
+
+    #[test]
+    fn layout() {
+        let grid = Grid3::from_fn([2, 3, 4], |p| p);
+        for (p, value) in grid.positions() {
+            assert_eq!(p, *value);
+        }
+        assert_eq!(grid.positions().count(), 24);
+        assert_eq!(grid.get(&PositionN([1, 2, 4])), None);
+    }
+
+    #[test]
+    fn surface_area() {
+        // Droplet of 13 cubes with a single inner air pocket.
+        let cubes = [
+            [2, 2, 2],
+            [1, 2, 2],
+            [3, 2, 2],
+            [2, 1, 2],
+            [2, 3, 2],
+            [2, 2, 1],
+            [2, 2, 3],
+            [2, 2, 4],
+            [2, 2, 6],
+            [1, 2, 5],
+            [3, 2, 5],
+            [2, 1, 5],
+            [2, 3, 5],
+        ];
+        let mut grid = Grid3::filled([4, 4, 7], false);
+        for cube in cubes {
+            grid[&PositionN(cube)] = true;
+        }
+        let area: usize = cubes
+            .iter()
+            .map(|&cube| {
+                6 - grid
+                    .neighbors_orthogonal(PositionN(cube))
+                    .filter(|(_, &c)| c)
+                    .count()
+            })
+            .sum();
+        assert_eq!(area, 64);
+        let around = grid
+            .neighbors_all(PositionN([2, 2, 2]))
+            .filter(|(_, &c)| c)
+            .count();
+        assert_eq!(around, 6);
+    }
+
+    #[test]
+    fn planar() {
+        let position = PositionN::from(Position { x: 3, y: 1 });
+        let moved = position
+            .checked_move(Movement { x: -3, y: 2 }.into())
+            .unwrap();
+        assert_eq!(Position::from(moved), Position { x: 0, y: 3 });
+        assert_eq!(position.checked_move(MovementN([0, -2])), None);
+    }
+}
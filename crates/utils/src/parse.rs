@@ -0,0 +1,134 @@
+//! Reusable [`nom`] parsers for common puzzle input shapes.
+
+use std::str::FromStr;
+
+use nom::{
+    bytes::complete::{tag, take_till1},
+    character::complete::{digit1, line_ending, one_of, space1},
+    combinator::{map, map_res, opt, recognize},
+    multi::{many1_count, separated_list1},
+    sequence::{pair, preceded, separated_pair},
+    IResult,
+};
+
+use crate::{grid::Grid, movement::Movement, position::Position};
+
+/// Integer with an optional `+` or `-` sign.
+pub fn integer<T: FromStr>(input: &str) -> IResult<&str, T> {
+    map_res(recognize(pair(opt(one_of("+-")), digit1)), str::parse)(input)
+}
+
+/// Integer without a sign.
+fn unsigned<T: FromStr>(input: &str) -> IResult<&str, T> {
+    map_res(digit1, str::parse)(input)
+}
+
+/// Integers on a single line, separated by spaces or tabs.
+pub fn integers<T: FromStr>(input: &str) -> IResult<&str, Vec<T>> {
+    separated_list1(space1, integer)(input)
+}
+
+/// Pair of unsigned coordinates written `{prefix}x{separator}y`, without signs.
+pub fn unsigned_pair<'a>(
+    prefix: &'a str,
+    separator: &'a str,
+) -> impl FnMut(&'a str) -> IResult<&'a str, (usize, usize)> {
+    preceded(
+        tag(prefix),
+        separated_pair(unsigned, tag(separator), unsigned),
+    )
+}
+
+/// Pair of signed coordinates written `{prefix}x{separator}y`.
+pub fn signed_pair<'a>(
+    prefix: &'a str,
+    separator: &'a str,
+) -> impl FnMut(&'a str) -> IResult<&'a str, (isize, isize)> {
+    preceded(
+        tag(prefix),
+        separated_pair(integer, tag(separator), integer),
+    )
+}
+
+/// [`Position`] written as an [`unsigned_pair`].
+pub fn position<'a>(
+    prefix: &'a str,
+    separator: &'a str,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Position> {
+    map(unsigned_pair(prefix, separator), |(x, y)| Position { x, y })
+}
+
+/// [`Movement`] written as a [`signed_pair`].
+pub fn movement<'a>(
+    prefix: &'a str,
+    separator: &'a str,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Movement> {
+    map(signed_pair(prefix, separator), |(x, y)| Movement { x, y })
+}
+
+/// Grid made of the following non-empty lines, stopping at a blank line or the
+/// end of input. Each character is converted with `f`, see [`Grid::parse`].
+pub fn grid<'a, T, E, F>(mut f: F) -> impl FnMut(&'a str) -> IResult<&'a str, Grid<T>>
+where
+    F: FnMut(char) -> Result<T, E>,
+{
+    move |input| {
+        map_res(
+            recognize(separated_list1(
+                line_ending,
+                take_till1(|c| c == '\n' || c == '\r'),
+            )),
+            |text| Grid::parse(text, &mut f),
+        )(input)
+    }
+}
+
+/// Items parsed by `block`, separated by one or more blank lines.
+pub fn blocks<'a, O, F>(block: F) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>>
+where
+    F: FnMut(&'a str) -> IResult<&'a str, O>,
+{
+    separated_list1(pair(line_ending, many1_count(line_ending)), block)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("1 -2\t+3" => Ok(("", vec![1, -2, 3])))]
+    #[test_case("7  8\n9" => Ok(("\n9", vec![7, 8])))]
+    fn integer_list(input: &str) -> IResult<&str, Vec<i64>> {
+        integers(input)
+    }
+
+    #[test]
+    fn pairs() {
+        assert_eq!(
+            position("p=", ",")("p=0,4 v=3,-3"),
+            Ok((" v=3,-3", Position { x: 0, y: 4 }))
+        );
+        assert_eq!(
+            movement("v=", ",")("v=3,-3"),
+            Ok(("", Movement { x: 3, y: -3 }))
+        );
+        assert_eq!(signed_pair("X", ", Y")("X+94, Y+34"), Ok(("", (94, 34))));
+        assert!(unsigned_pair("p=", ",")("p=-1,4").is_err());
+        assert!(unsigned_pair("X+", ", Y+")("X++94, Y+34").is_err());
+        assert_eq!(
+            unsigned_pair("X+", ", Y+")("X+94, Y+34"),
+            Ok(("", (94, 34)))
+        );
+    }
+
+    #[test]
+    fn grids_in_blocks() {
+        let input = "#.\n.#\n\n\n##\r\n..\n";
+        let (rest, grids) = blocks(grid(|c| Ok::<_, ()>(c == '#')))(input).unwrap();
+        assert_eq!(rest, "\n");
+        assert_eq!(grids.len(), 2);
+        assert_eq!(grids[0].iter().filter(|&&b| b).count(), 2);
+        assert_eq!(grids[1].row(0).to_vec(), vec![true, true]);
+        assert!(grid(Ok::<_, ()>)("ab\nc").is_err());
+    }
+}
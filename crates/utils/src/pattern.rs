@@ -0,0 +1,203 @@
+use crate::{
+    grid::{Grid, GridError},
+    position::Position,
+};
+
+/// Rigid transformation of a pattern: an optional left to right mirror followed by
+/// clockwise quarter turns.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Orientation {
+    pub flipped: bool,
+    pub quarter_turns: u8,
+}
+
+impl Orientation {
+    /// The eight orientations of a rectangle, identity first.
+    pub const ALL: [Orientation; 8] = [
+        Orientation::new(false, 0),
+        Orientation::new(false, 1),
+        Orientation::new(false, 2),
+        Orientation::new(false, 3),
+        Orientation::new(true, 0),
+        Orientation::new(true, 1),
+        Orientation::new(true, 2),
+        Orientation::new(true, 3),
+    ];
+
+    const fn new(flipped: bool, quarter_turns: u8) -> Self {
+        Self {
+            flipped,
+            quarter_turns,
+        }
+    }
+
+    fn apply<T: Clone>(self, grid: &Grid<T>) -> Grid<T> {
+        let mut out = if self.flipped {
+            grid.flip_h()
+        } else {
+            grid.clone()
+        };
+        for _ in 0..self.quarter_turns % 4 {
+            out = out.rotate_cw();
+        }
+        out
+    }
+}
+
+/// Occurrence of a pattern: top-left corner of the matching window and the
+/// orientation the pattern was matched with.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Match {
+    pub position: Position,
+    pub orientation: Orientation,
+}
+
+/// Rectangular template of bytes, `None` cells match anything.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Pattern {
+    cells: Grid<Option<u8>>,
+}
+
+impl Pattern {
+    pub fn new(cells: Grid<Option<u8>>) -> Self {
+        Self { cells }
+    }
+
+    /// Create a pattern from text, `wildcard` standing for cells matching
+    /// anything.
+    pub fn parse(template: &str, wildcard: char) -> Result<Self, GridError<char>> {
+        Grid::parse(template, |c| match c {
+            c if c == wildcard => Ok(None),
+            c => u8::try_from(c).map(Some).map_err(|_| c),
+        })
+        .map(Self::new)
+    }
+
+    pub fn width(&self) -> usize {
+        self.cells.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.cells.height
+    }
+
+    /// Copy of the pattern transformed by `orientation`.
+    pub fn oriented(&self, orientation: Orientation) -> Self {
+        Self::new(orientation.apply(&self.cells))
+    }
+
+    /// Every distinct transformation of the pattern, with the first orientation
+    /// producing it.
+    pub fn orientations(&self) -> Vec<(Orientation, Pattern)> {
+        let mut out: Vec<(Orientation, Pattern)> = Vec::with_capacity(8);
+        for orientation in Orientation::ALL {
+            let pattern = self.oriented(orientation);
+            if out.iter().all(|(_, p)| *p != pattern) {
+                out.push((orientation, pattern));
+            }
+        }
+        out
+    }
+
+    /// Top-left corners of the occurrences of the pattern as is, in row-major
+    /// order.
+    pub fn find_in<'a>(&'a self, grid: &'a Grid<u8>) -> impl Iterator<Item = Position> + 'a {
+        let fixed: Vec<(Position, u8)> = self
+            .cells
+            .positions()
+            .filter_map(|(p, c)| Some((p, (*c)?)))
+            .collect();
+        let columns = (grid.width + 1).saturating_sub(self.width());
+        let rows = (grid.height + 1).saturating_sub(self.height());
+        (0..rows)
+            .flat_map(move |y| (0..columns).map(move |x| Position { x, y }))
+            .filter(move |origin| {
+                fixed.iter().all(|(p, c)| {
+                    grid[&Position {
+                        x: origin.x + p.x,
+                        y: origin.y + p.y,
+                    }] == *c
+                })
+            })
+    }
+
+    /// Occurrences of the pattern in `grid`, in every distinct orientation when
+    /// `all_orientations` is set.
+    pub fn find_all(&self, grid: &Grid<u8>, all_orientations: bool) -> Vec<Match> {
+        let orientations = if all_orientations {
+            self.orientations()
+        } else {
+            vec![(Orientation::default(), self.clone())]
+        };
+        orientations
+            .iter()
+            .flat_map(|(orientation, pattern)| {
+                pattern.find_in(grid).map(|position| Match {
+                    position,
+                    orientation: *orientation,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    const TEST: &str = "MMMSXXMASM
+MSAMXMSMSA
+AMXSXMAAMM
+MSAMASMSMX
+XMASAMXAMM
+XXAMMXXAMA
+SMSMSASXSS
+SAXAMASAAA
+MAMMMXMMMM
+MXMXAXMASX";
+
+    fn grid(input: &str) -> Grid<u8> {
+        Grid::parse(input, u8::try_from).unwrap()
+    }
+
+    #[test_case("XMAS" => 4)]
+    #[test_case("X...\n.M..\n..A.\n...S" => 4)]
+    #[test_case("M.S\n.A.\nM.S" => 4)]
+    #[test_case("A" => 1)]
+    #[test_case("AB\nCD" => 8)]
+    fn orientations(template: &str) -> usize {
+        Pattern::parse(template, '.').unwrap().orientations().len()
+    }
+
+    #[test]
+    fn word_search() {
+        let grid = grid(TEST);
+        let line = Pattern::parse("XMAS", '.').unwrap();
+        let diagonal = Pattern::parse("X...\n.M..\n..A.\n...S", '.').unwrap();
+        let count = line.find_all(&grid, true).len() + diagonal.find_all(&grid, true).len();
+        assert_eq!(count, 18);
+        assert_eq!(line.find_all(&grid, false).len(), 3);
+    }
+
+    #[test]
+    fn cross() {
+        let grid = grid(TEST);
+        let cross = Pattern::parse("M.S\n.A.\nM.S", '.').unwrap();
+        let matches = cross.find_all(&grid, true);
+        assert_eq!(matches.len(), 9);
+        assert_eq!(
+            matches[0],
+            Match {
+                position: Position { x: 1, y: 0 },
+                orientation: Orientation::default()
+            }
+        );
+    }
+
+    #[test]
+    fn too_large() {
+        let pattern = Pattern::parse("XMAS", '.').unwrap();
+        assert_eq!(pattern.find_in(&grid("XMA")).count(), 0);
+    }
+}
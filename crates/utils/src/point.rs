@@ -0,0 +1,182 @@
+use std::{
+    num::TryFromIntError,
+    ops::{Add, Mul, Neg, Rem, Sub},
+};
+
+use crate::{movement::Movement, position::Position};
+
+/// Signed coordinates, for positions that may lie left or above the origin.
+///
+/// Unlike [`Position`], arithmetic never leaves the type: intermediate results may
+/// be negative and be brought back later, for instance with `%`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point {
+    pub x: isize,
+    pub y: isize,
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Point) -> Self::Output {
+        Point {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y,
+        }
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, rhs: Point) -> Self::Output {
+        Point {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+        }
+    }
+}
+
+impl Add<Movement> for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Movement) -> Self::Output {
+        self + Point::from(rhs)
+    }
+}
+
+impl Sub<Movement> for Point {
+    type Output = Point;
+
+    fn sub(self, rhs: Movement) -> Self::Output {
+        self - Point::from(rhs)
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Self::Output {
+        Point {
+            x: -self.x,
+            y: -self.y,
+        }
+    }
+}
+
+impl Mul<isize> for Point {
+    type Output = Point;
+
+    fn mul(self, rhs: isize) -> Self::Output {
+        Point {
+            x: self.x * rhs,
+            y: self.y * rhs,
+        }
+    }
+}
+
+/// Euclidean remainder on each axis, the result is never negative.
+impl Rem for Point {
+    type Output = Point;
+
+    fn rem(self, rhs: Point) -> Self::Output {
+        Point {
+            x: self.x.rem_euclid(rhs.x),
+            y: self.y.rem_euclid(rhs.y),
+        }
+    }
+}
+
+impl From<Movement> for Point {
+    fn from(value: Movement) -> Self {
+        Point {
+            x: value.x,
+            y: value.y,
+        }
+    }
+}
+
+impl From<Point> for Movement {
+    fn from(value: Point) -> Self {
+        Movement {
+            x: value.x,
+            y: value.y,
+        }
+    }
+}
+
+impl TryFrom<Position> for Point {
+    type Error = TryFromIntError;
+
+    fn try_from(value: Position) -> Result<Self, Self::Error> {
+        Ok(Point {
+            x: value.x.try_into()?,
+            y: value.y.try_into()?,
+        })
+    }
+}
+
+/// Fails on negative coordinates.
+impl TryFrom<Point> for Position {
+    type Error = TryFromIntError;
+
+    fn try_from(value: Point) -> Result<Self, Self::Error> {
+        Ok(Position {
+            x: value.x.try_into()?,
+            y: value.y.try_into()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test]
+    fn arithmetic() {
+        let a = Point { x: 2, y: -3 };
+        let b = Point { x: -5, y: 1 };
+        assert_eq!(a + b, Point { x: -3, y: -2 });
+        assert_eq!(a - b, Point { x: 7, y: -4 });
+        assert_eq!(-a, Point { x: -2, y: 3 });
+        assert_eq!(a * 3, Point { x: 6, y: -9 });
+        assert_eq!(a + Movement { x: 1, y: 1 }, Point { x: 3, y: -2 });
+        assert_eq!(a - Movement { x: 1, y: 1 }, Point { x: 1, y: -4 });
+    }
+
+    #[test_case(-1, -8 => (10, 6))]
+    #[test_case(11, 7 => (0, 0))]
+    #[test_case(25, 3 => (3, 3))]
+    fn rem(x: isize, y: isize) -> (isize, isize) {
+        let p = Point { x, y } % Point { x: 11, y: 7 };
+        (p.x, p.y)
+    }
+
+    #[test]
+    fn robot() {
+        // Day 14 example robot, after 5 seconds on an 11x7 field.
+        let start = Point { x: 2, y: 4 };
+        let velocity = Movement { x: 2, y: -3 };
+        let end = (start + Point::from(velocity) * 5) % Point { x: 11, y: 7 };
+        assert_eq!(Position::try_from(end), Ok(Position { x: 1, y: 3 }));
+    }
+
+    #[test]
+    fn conversions() {
+        assert!(Position::try_from(Point { x: -1, y: 0 }).is_err());
+        assert_eq!(
+            Point::try_from(Position { x: 3, y: 4 }),
+            Ok(Point { x: 3, y: 4 })
+        );
+        assert!(Point::try_from(Position {
+            x: usize::MAX,
+            y: 0
+        })
+        .is_err());
+        assert_eq!(
+            Movement::from(Point { x: -1, y: 2 }),
+            Movement { x: -1, y: 2 }
+        );
+    }
+}
@@ -1,6 +1,8 @@
-use std::ops::Add;
+use std::{ops::Add, str::FromStr};
 
-use crate::movement::Movement;
+use nom::{combinator::all_consuming, error::Error, Finish};
+
+use crate::{movement::Movement, parse};
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Position {
@@ -15,6 +17,28 @@ impl Position {
             y: self.y.checked_add_signed(movement.y)?,
         })
     }
+
+    /// Number of orthogonal steps between two positions.
+    pub fn manhattan_distance(&self, other: Position) -> usize {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    /// Number of steps between two positions when diagonal steps are allowed.
+    pub fn chebyshev_distance(&self, other: Position) -> usize {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+}
+
+/// Parse `x,y`.
+impl FromStr for Position {
+    type Err = Error<String>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        all_consuming(parse::position("", ","))(s)
+            .finish()
+            .map(|(_, v)| v)
+            .map_err(|e| Error::new(e.input.to_owned(), e.code))
+    }
 }
 
 impl Add<Movement> for Position {
@@ -38,3 +62,24 @@ impl Add<&Movement> for Position {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(Position { x: 3, y: 4 }, Position { x: 3, y: 4 } => (0, 0))]
+    #[test_case(Position { x: 1, y: 7 }, Position { x: 4, y: 2 } => (8, 5))]
+    #[test_case(Position { x: 6, y: 0 }, Position { x: 0, y: 6 } => (12, 6))]
+    fn distances(a: Position, b: Position) -> (usize, usize) {
+        assert_eq!(a.manhattan_distance(b), b.manhattan_distance(a));
+        (a.manhattan_distance(b), a.chebyshev_distance(b))
+    }
+
+    #[test_case("3,14" => Some(Position { x: 3, y: 14 }))]
+    #[test_case("3,-1" => None)]
+    #[test_case("3,1 " => None ; "trailing space")]
+    fn from_str(s: &str) -> Option<Position> {
+        s.parse().ok()
+    }
+}
@@ -0,0 +1,189 @@
+use crate::position::Position;
+
+/// Axis-aligned rectangle of positions, `width` x `height` cells from its
+/// top-left corner `origin`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Rect {
+    pub origin: Position,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Rect {
+    pub fn new(origin: Position, width: usize, height: usize) -> Self {
+        Self {
+            origin,
+            width,
+            height,
+        }
+    }
+
+    /// Rectangle of the given size with its corner at `(0, 0)`.
+    pub fn sized(width: usize, height: usize) -> Self {
+        Self::new(Position { x: 0, y: 0 }, width, height)
+    }
+
+    /// Smallest rectangle holding every position, `None` if there are none.
+    pub fn bounding(positions: impl IntoIterator<Item = Position>) -> Option<Self> {
+        let mut positions = positions.into_iter();
+        let first = positions.next()?;
+        let (min, max) = positions.fold((first, first), |(min, max), p| {
+            (
+                Position {
+                    x: min.x.min(p.x),
+                    y: min.y.min(p.y),
+                },
+                Position {
+                    x: max.x.max(p.x),
+                    y: max.y.max(p.y),
+                },
+            )
+        });
+        Some(Self::new(min, max.x - min.x + 1, max.y - min.y + 1))
+    }
+
+    pub fn area(&self) -> usize {
+        self.width * self.height
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.width == 0 || self.height == 0
+    }
+
+    pub fn contains(&self, p: Position) -> bool {
+        (self.origin.x..self.origin.x + self.width).contains(&p.x)
+            && (self.origin.y..self.origin.y + self.height).contains(&p.y)
+    }
+
+    /// Overlap of both rectangles, `None` if they do not share any cell.
+    pub fn intersect(&self, other: &Rect) -> Option<Rect> {
+        let x = self.origin.x.max(other.origin.x);
+        let y = self.origin.y.max(other.origin.y);
+        let right = (self.origin.x + self.width).min(other.origin.x + other.width);
+        let bottom = (self.origin.y + self.height).min(other.origin.y + other.height);
+        (x < right && y < bottom).then(|| Rect::new(Position { x, y }, right - x, bottom - y))
+    }
+
+    /// Top-left, top-right, bottom-left and bottom-right quarters.
+    ///
+    /// An odd dimension has a middle row or column, which belongs to none of the
+    /// quarters. An even dimension is split in two halves and every cell belongs
+    /// to a quarter.
+    pub fn split_into_quadrants(&self) -> [Rect; 4] {
+        let (w, h) = (self.width / 2, self.height / 2);
+        let right = self.origin.x + self.width - w;
+        let bottom = self.origin.y + self.height - h;
+        [
+            Rect::new(self.origin, w, h),
+            Rect::new(
+                Position {
+                    x: right,
+                    ..self.origin
+                },
+                w,
+                h,
+            ),
+            Rect::new(
+                Position {
+                    y: bottom,
+                    ..self.origin
+                },
+                w,
+                h,
+            ),
+            Rect::new(
+                Position {
+                    x: right,
+                    y: bottom,
+                },
+                w,
+                h,
+            ),
+        ]
+    }
+
+    /// Closest position inside the rectangle, its origin if it is empty.
+    pub fn clamp(&self, p: Position) -> Position {
+        Position {
+            x: p.x
+                .clamp(self.origin.x, self.origin.x + self.width.saturating_sub(1)),
+            y: p.y
+                .clamp(self.origin.y, self.origin.y + self.height.saturating_sub(1)),
+        }
+    }
+
+    /// Every position in the rectangle, in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = Position> {
+        let Rect {
+            origin,
+            width,
+            height,
+        } = *self;
+        (origin.y..origin.y + height)
+            .flat_map(move |y| (origin.x..origin.x + width).map(move |x| Position { x, y }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    const RECT: Rect = Rect {
+        origin: Position { x: 2, y: 1 },
+        width: 4,
+        height: 3,
+    };
+
+    #[test_case(Position { x: 2, y: 1 } => true)]
+    #[test_case(Position { x: 5, y: 3 } => true)]
+    #[test_case(Position { x: 6, y: 3 } => false)]
+    #[test_case(Position { x: 3, y: 4 } => false)]
+    #[test_case(Position { x: 1, y: 2 } => false)]
+    fn contains(p: Position) -> bool {
+        RECT.contains(p)
+    }
+
+    #[test_case(Rect::sized(4, 3) => Some(Rect::new(Position { x: 2, y: 1 }, 2, 2)))]
+    #[test_case(Rect::new(Position { x: 5, y: 3 }, 10, 10) => Some(Rect::new(Position { x: 5, y: 3 }, 1, 1)))]
+    #[test_case(Rect::new(Position { x: 6, y: 0 }, 10, 10) => None)]
+    fn intersect(other: Rect) -> Option<Rect> {
+        assert_eq!(RECT.intersect(&other), other.intersect(&RECT));
+        RECT.intersect(&other)
+    }
+
+    #[test]
+    fn quadrants() {
+        let quadrants = Rect::sized(11, 7).split_into_quadrants();
+        assert_eq!(quadrants[0], Rect::sized(5, 3));
+        assert_eq!(quadrants[3], Rect::new(Position { x: 6, y: 4 }, 5, 3));
+        let covered: usize = quadrants.iter().map(Rect::area).sum();
+        assert_eq!(covered, 11 * 7 - 11 - 7 + 1);
+        assert!(quadrants
+            .iter()
+            .all(|q| !q.contains(Position { x: 5, y: 0 }) && !q.contains(Position { x: 0, y: 3 })));
+
+        let even = Rect::sized(4, 2).split_into_quadrants();
+        assert_eq!(even.iter().map(Rect::area).sum::<usize>(), 8);
+    }
+
+    #[test_case(4, 2 => [(0, 0, 2, 1), (2, 0, 2, 1), (0, 1, 2, 1), (2, 1, 2, 1)])]
+    #[test_case(4, 3 => [(0, 0, 2, 1), (2, 0, 2, 1), (0, 2, 2, 1), (2, 2, 2, 1)])]
+    #[test_case(5, 2 => [(0, 0, 2, 1), (3, 0, 2, 1), (0, 1, 2, 1), (3, 1, 2, 1)])]
+    fn quadrants_even(width: usize, height: usize) -> [(usize, usize, usize, usize); 4] {
+        let origin = Position { x: 10, y: 20 };
+        Rect::new(origin, width, height)
+            .split_into_quadrants()
+            .map(|q| (q.origin.x - 10, q.origin.y - 20, q.width, q.height))
+    }
+
+    #[test]
+    fn clamp_and_iter() {
+        assert_eq!(RECT.clamp(Position { x: 0, y: 9 }), Position { x: 2, y: 3 });
+        assert_eq!(RECT.clamp(Position { x: 4, y: 2 }), Position { x: 4, y: 2 });
+        assert_eq!(RECT.iter().count(), RECT.area());
+        assert!(RECT.iter().all(|p| RECT.contains(p)));
+        assert_eq!(Rect::bounding(RECT.iter()), Some(RECT));
+        assert_eq!(Rect::bounding([]), None);
+    }
+}
@@ -0,0 +1,347 @@
+use std::collections::HashSet;
+
+use crate::{
+    components::Connectivity,
+    grid::{BitGrid, Grid},
+    movement::{Movement, CLOCKWISE},
+    position::Position,
+    rect::Rect,
+};
+
+/// Pairs of orthogonal movements around each corner of a cell, clockwise from the
+/// top-right one.
+const CORNERS: [(Movement, Movement); 4] = [
+    (CLOCKWISE[0], CLOCKWISE[1]),
+    (CLOCKWISE[1], CLOCKWISE[2]),
+    (CLOCKWISE[2], CLOCKWISE[3]),
+    (CLOCKWISE[3], CLOCKWISE[0]),
+];
+
+/// Arbitrary set of cells, seen as a shape made of unit squares.
+///
+/// Cells are connected through their edges, so two cells touching by a corner only
+/// belong to distinct components. Holes are the areas of the complement that
+/// cannot reach the outside, even diagonally.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Region {
+    /// Smallest rectangle holding every cell.
+    bounds: Rect,
+    /// Cells, relative to the origin of `bounds`.
+    cells: BitGrid,
+}
+
+impl Region {
+    pub fn new(cells: HashSet<Position>) -> Self {
+        cells.into_iter().collect()
+    }
+
+    /// Smallest rectangle holding every cell, empty for an empty region.
+    pub fn bounds(&self) -> Rect {
+        self.bounds
+    }
+
+    /// Add a cell, returns false if it was already present.
+    pub fn insert(&mut self, position: Position) -> bool {
+        if !self.bounds.contains(position) {
+            self.grow(position);
+        }
+        self.cells.insert(self.relative(position))
+    }
+
+    /// Cells, in row-major order.
+    pub fn cells(&self) -> impl Iterator<Item = Position> + '_ {
+        let origin = self.bounds.origin;
+        self.cells.iter().map(move |p| Position {
+            x: p.x + origin.x,
+            y: p.y + origin.y,
+        })
+    }
+
+    pub fn contains(&self, position: &Position) -> bool {
+        self.bounds.contains(*position) && self.cells.contains(self.relative(*position))
+    }
+
+    /// Number of cells.
+    pub fn area(&self) -> usize {
+        self.cells.count()
+    }
+
+    /// Number of cell edges between the region and the outside.
+    pub fn perimeter(&self) -> usize {
+        self.cells()
+            .map(|p| {
+                CLOCKWISE
+                    .iter()
+                    .filter(|m| !self.has_neighbor(&p, **m))
+                    .count()
+            })
+            .sum()
+    }
+
+    /// Number of corners on the outline, convex or concave.
+    pub fn corners(&self) -> usize {
+        self.cells()
+            .map(|p| {
+                CORNERS
+                    .iter()
+                    .filter(|(d1, d2)| self.is_corner(&p, *d1, *d2))
+                    .count()
+            })
+            .sum()
+    }
+
+    /// Number of straight sides on the outline, holes included.
+    ///
+    /// A closed rectilinear outline has as many sides as corners.
+    pub fn sides(&self) -> usize {
+        self.corners()
+    }
+
+    /// Number of edge-connected components.
+    pub fn components(&self) -> usize {
+        self.topology().0
+    }
+
+    /// Number of enclosed holes.
+    pub fn holes(&self) -> usize {
+        self.topology().1
+    }
+
+    /// Components minus holes.
+    pub fn euler_characteristic(&self) -> isize {
+        let (components, holes) = self.topology();
+        components as isize - holes as isize
+    }
+
+    /// Extend the bounds to hold `position`, moving the existing cells.
+    fn grow(&mut self, position: Position) {
+        let bounds = if self.cells.is_empty() {
+            Rect::new(position, 1, 1)
+        } else {
+            let far = Position {
+                x: self.bounds.origin.x + self.bounds.width - 1,
+                y: self.bounds.origin.y + self.bounds.height - 1,
+            };
+            Rect::bounding([self.bounds.origin, far, position]).unwrap()
+        };
+        let old = std::mem::replace(self, Self::with_bounds(bounds));
+        for p in old.cells() {
+            self.cells.insert(self.relative(p));
+        }
+    }
+
+    fn with_bounds(bounds: Rect) -> Self {
+        Self {
+            bounds,
+            cells: BitGrid::new(bounds.width, bounds.height),
+        }
+    }
+
+    fn relative(&self, position: Position) -> Position {
+        Position {
+            x: position.x - self.bounds.origin.x,
+            y: position.y - self.bounds.origin.y,
+        }
+    }
+
+    fn has_neighbor(&self, position: &Position, movement: Movement) -> bool {
+        position
+            .checked_move(movement)
+            .is_some_and(|n| self.contains(&n))
+    }
+
+    fn is_corner(&self, position: &Position, d1: Movement, d2: Movement) -> bool {
+        let diagonal = Movement {
+            x: d1.x + d2.x,
+            y: d1.y + d2.y,
+        };
+        let a = self.has_neighbor(position, d1);
+        let b = self.has_neighbor(position, d2);
+        let c = self.has_neighbor(position, diagonal);
+        (!a && !b) || (a && b && !c)
+    }
+
+    /// Count components and holes on a rasterised copy of the region, surrounded by
+    /// a one cell wide margin so that the outside is a single component.
+    fn topology(&self) -> (usize, usize) {
+        if self.cells.is_empty() {
+            return (0, 0);
+        }
+        let Rect { width, height, .. } = self.bounds;
+        let raster = Grid::from_fn(width + 2, height + 2, |p| {
+            (1..=width).contains(&p.x)
+                && (1..=height).contains(&p.y)
+                && self.cells.contains(Position {
+                    x: p.x - 1,
+                    y: p.y - 1,
+                })
+        });
+        let components = raster
+            .components(Connectivity::Four)
+            .regions
+            .iter()
+            .filter(|r| r.value)
+            .count();
+        let outside = raster
+            .components(Connectivity::Eight)
+            .regions
+            .iter()
+            .filter(|r| !r.value)
+            .count();
+        (components, outside - 1)
+    }
+}
+
+impl Default for Region {
+    fn default() -> Self {
+        Self::with_bounds(Rect::sized(0, 0))
+    }
+}
+
+/// Size the bounds once for every cell instead of growing them on each insert.
+impl FromIterator<Position> for Region {
+    fn from_iter<I: IntoIterator<Item = Position>>(iter: I) -> Self {
+        let cells: Vec<_> = iter.into_iter().collect();
+        let mut region = Rect::bounding(cells.iter().copied())
+            .map(Self::with_bounds)
+            .unwrap_or_default();
+        region.extend(cells);
+        region
+    }
+}
+
+impl Extend<Position> for Region {
+    fn extend<I: IntoIterator<Item = Position>>(&mut self, iter: I) {
+        for position in iter {
+            self.insert(position);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    const SIMPLE: &str = "AAAA
+BBCD
+BBCC
+EEEC";
+
+    const INCLUSION: &str = "OOOOO
+OXOXO
+OOOOO
+OXOXO
+OOOOO";
+
+    const HARD: &str = "RRRRIICCFF
+RRRRIICCCF
+VVRRRCCFFF
+VVRCCCJFFF
+VVVVCJJCFE
+VVIVCCJJEE
+VVIIICJJEE
+MIIIIIJJEE
+MIIISIJEEE
+MMMISSJEEE";
+
+    const E: &str = "EEEEE
+EXXXX
+EEEEE
+EXXXX
+EEEEE
+";
+
+    const ALT: &str = "AAAAAA
+AAABBA
+AAABBA
+ABBAAA
+ABBAAA
+AAAAAA
+";
+
+    fn regions(input: &str) -> Vec<Region> {
+        let grid = Grid::parse(input, Ok::<_, ()>).unwrap();
+        let components = grid.components(Connectivity::Four);
+        (0..components.regions.len())
+            .map(|id| components.cells(id).collect())
+            .collect()
+    }
+
+    #[test_case(SIMPLE => 140)]
+    #[test_case(INCLUSION => 772)]
+    #[test_case(HARD => 1930)]
+    fn perimeter_price(input: &str) -> usize {
+        regions(input)
+            .iter()
+            .map(|r| r.area() * r.perimeter())
+            .sum()
+    }
+
+    #[test_case(SIMPLE => 80)]
+    #[test_case(INCLUSION => 436)]
+    #[test_case(E => 236)]
+    #[test_case(ALT => 368)]
+    #[test_case(HARD => 1206)]
+    fn sides_price(input: &str) -> usize {
+        regions(input).iter().map(|r| r.area() * r.sides()).sum()
+    }
+
+    // Both B blocks of ALT touch by a corner, they form a single hole in A.
+    #[test_case(SIMPLE => vec![0, 0, 0, 0, 0])]
+    #[test_case(INCLUSION => vec![4, 0, 0, 0, 0])]
+    #[test_case(E => vec![0, 0, 0])]
+    #[test_case(ALT => vec![1, 0, 0])]
+    fn holes(input: &str) -> Vec<usize> {
+        regions(input).iter().map(Region::holes).collect()
+    }
+
+    #[test_case(INCLUSION => vec![-3, 1, 1, 1, 1])]
+    #[test_case(ALT => vec![0, 1, 1])]
+    fn euler_characteristic(input: &str) -> Vec<isize> {
+        regions(input)
+            .iter()
+            .map(Region::euler_characteristic)
+            .collect()
+    }
+
+    #[test]
+    fn diagonal_touch() {
+        // Two cells sharing a corner are two components and do not enclose
+        // anything.
+        let region: Region = [Position { x: 0, y: 0 }, Position { x: 1, y: 1 }]
+            .into_iter()
+            .collect();
+        assert_eq!(region.components(), 2);
+        assert_eq!(region.holes(), 0);
+        assert_eq!(region.perimeter(), 8);
+        assert_eq!(region.corners(), 8);
+    }
+
+    #[test]
+    fn empty() {
+        let region = Region::default();
+        assert_eq!(region.area(), 0);
+        assert_eq!(region.euler_characteristic(), 0);
+    }
+
+    #[test]
+    fn grow() {
+        let cells = [
+            Position { x: 4, y: 3 },
+            Position { x: 6, y: 1 },
+            Position { x: 2, y: 5 },
+        ];
+        let mut region = Region::default();
+        for p in cells {
+            assert!(region.insert(p));
+        }
+        assert!(!region.insert(cells[0]));
+        assert_eq!(region.bounds(), Rect::new(Position { x: 2, y: 1 }, 5, 5));
+        assert!(cells.iter().all(|p| region.contains(p)));
+        assert!(!region.contains(&Position { x: 0, y: 0 }));
+        assert_eq!(region.area(), 3);
+        assert_eq!(region, cells.into_iter().collect());
+        assert_eq!(region, Region::new(cells.into_iter().collect()));
+    }
+}
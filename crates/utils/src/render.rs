@@ -0,0 +1,260 @@
+use std::{
+    fmt::{Display, Write as _},
+    io::{self, IsTerminal, Write},
+};
+
+use crate::{grid::Grid, image::Rgb, movement::Movement, position::Position};
+
+/// Terminal colour, either one of the 8 standard ANSI ones or a 24-bit one.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Colour {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    Rgb(Rgb),
+}
+
+impl Colour {
+    /// Distinct colours, cycled through by [`Colour::palette`].
+    const PALETTE: [Colour; 12] = [
+        Colour::Red,
+        Colour::Green,
+        Colour::Yellow,
+        Colour::Blue,
+        Colour::Magenta,
+        Colour::Cyan,
+        Colour::Rgb([255, 135, 0]),
+        Colour::Rgb([135, 95, 255]),
+        Colour::Rgb([0, 175, 95]),
+        Colour::Rgb([255, 95, 135]),
+        Colour::Rgb([135, 175, 255]),
+        Colour::Rgb([175, 135, 0]),
+    ];
+
+    /// Colour number `index` of a palette meant to tell regions apart.
+    pub fn palette(index: usize) -> Colour {
+        Self::PALETTE[index % Self::PALETTE.len()]
+    }
+
+    fn sgr(self, background: bool) -> String {
+        let base = if background { 40 } else { 30 };
+        match self {
+            Colour::Black => format!("{}", base),
+            Colour::Red => format!("{}", base + 1),
+            Colour::Green => format!("{}", base + 2),
+            Colour::Yellow => format!("{}", base + 3),
+            Colour::Blue => format!("{}", base + 4),
+            Colour::Magenta => format!("{}", base + 5),
+            Colour::Cyan => format!("{}", base + 6),
+            Colour::White => format!("{}", base + 7),
+            Colour::Rgb([r, g, b]) => format!("{};2;{};{};{}", base + 8, r, g, b),
+        }
+    }
+}
+
+/// Overlays applied on top of a cell.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+struct Style {
+    glyph: Option<char>,
+    foreground: Option<Colour>,
+    background: Option<Colour>,
+}
+
+/// Terminal rendering of a grid, with overlays layered on top of its cells.
+///
+/// Overlays are applied in the order they were added, later ones winning.
+/// Colours are only emitted when asked to, or when printing to a terminal.
+pub struct Renderer<'a, T> {
+    grid: &'a Grid<T>,
+    text: Box<dyn Fn(&T) -> String + 'a>,
+    styles: Grid<Style>,
+}
+
+impl<'a, T: Display> Renderer<'a, T> {
+    /// Render cells with their [`Display`] implementation.
+    pub fn new(grid: &'a Grid<T>) -> Self {
+        Self::with_glyphs(grid, |cell| cell.to_string())
+    }
+}
+
+impl<'a, T> Renderer<'a, T> {
+    /// Render cells with the text returned by `text`.
+    pub fn with_glyphs<F>(grid: &'a Grid<T>, text: F) -> Self
+    where
+        F: Fn(&T) -> String + 'a,
+    {
+        Self {
+            grid,
+            text: Box::new(text),
+            styles: Grid::filled(grid.width, grid.height, Style::default()),
+        }
+    }
+
+    /// Paint cells with the colour returned by `colour`, if any.
+    pub fn colours<F>(mut self, mut colour: F) -> Self
+    where
+        F: FnMut(Position, &T) -> Option<Colour>,
+    {
+        for (p, cell) in self.grid.positions() {
+            if let Some(c) = colour(p, cell) {
+                self.styles[&p].foreground = Some(c);
+            }
+        }
+        self
+    }
+
+    /// Highlight the background of `positions`.
+    pub fn highlight<I>(mut self, positions: I, colour: Colour) -> Self
+    where
+        I: IntoIterator<Item = Position>,
+    {
+        for p in positions {
+            if let Some(style) = self.styles.get_mut(p) {
+                style.background = Some(colour);
+            }
+        }
+        self
+    }
+
+    /// Draw an arrow on each position pointing toward the following one, the last
+    /// position keeps the direction it was entered with.
+    pub fn path<I>(mut self, positions: I, colour: Colour) -> Self
+    where
+        I: IntoIterator<Item = Position>,
+    {
+        let positions: Vec<Position> = positions.into_iter().collect();
+        let mut last = None;
+        for (i, &p) in positions.iter().enumerate() {
+            let movement = positions
+                .get(i + 1)
+                .and_then(|&next| Movement::between(p, next))
+                .or(last);
+            if let Some(movement) = movement {
+                self.draw(p, movement, colour);
+            }
+            last = movement;
+        }
+        self
+    }
+
+    /// Draw an arrow for each position heading in the paired direction.
+    pub fn moves<I>(mut self, moves: I, colour: Colour) -> Self
+    where
+        I: IntoIterator<Item = (Position, Movement)>,
+    {
+        for (p, movement) in moves {
+            self.draw(p, movement, colour);
+        }
+        self
+    }
+
+    fn draw(&mut self, position: Position, movement: Movement, colour: Colour) {
+        if let Some(style) = self.styles.get_mut(position) {
+            style.glyph = Some(arrow(movement));
+            style.foreground = Some(colour);
+        }
+    }
+
+    /// Render the grid as text, with ANSI escape sequences when `ansi` is set.
+    pub fn render(&self, ansi: bool) -> String {
+        let mut out = String::new();
+        for (y, row) in self.grid.rows().enumerate() {
+            for (x, cell) in row.iter().enumerate() {
+                let style = self.styles[&Position { x, y }];
+                let text = match style.glyph {
+                    Some(glyph) => glyph.to_string(),
+                    None => (self.text)(cell),
+                };
+                let codes: Vec<String> = [
+                    style.foreground.map(|c| c.sgr(false)),
+                    style.background.map(|c| c.sgr(true)),
+                ]
+                .into_iter()
+                .flatten()
+                .collect();
+                if ansi && !codes.is_empty() {
+                    let _ = write!(out, "\x1b[{}m{}\x1b[0m", codes.join(";"), text);
+                } else {
+                    out.push_str(&text);
+                }
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Print to stdout, with colours only when it is a terminal.
+    pub fn print(&self) -> io::Result<()> {
+        let mut stdout = io::stdout().lock();
+        let ansi = stdout.is_terminal();
+        stdout.write_all(self.render(ansi).as_bytes())
+    }
+}
+
+fn arrow(movement: Movement) -> char {
+    match (movement.x.signum(), movement.y.signum()) {
+        (0, -1) => '↑',
+        (1, -1) => '↗',
+        (1, 0) => '→',
+        (1, 1) => '↘',
+        (0, 1) => '↓',
+        (-1, 1) => '↙',
+        (-1, 0) => '←',
+        (-1, -1) => '↖',
+        _ => '•',
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::movement::{RIGHT, UP};
+
+    fn grid() -> Grid<char> {
+        Grid::parse("...\n.#.\n...", Ok::<_, ()>).unwrap()
+    }
+
+    #[test]
+    fn plain() {
+        let grid = grid();
+        let renderer = Renderer::new(&grid)
+            .colours(|_, &c| (c == '#').then_some(Colour::Red))
+            .highlight([Position { x: 2, y: 2 }], Colour::Blue);
+        assert_eq!(renderer.render(false), grid.to_string());
+    }
+
+    #[test]
+    fn path() {
+        let grid = grid();
+        let path = [(0, 2), (0, 1), (0, 0), (1, 0), (2, 0)].map(|(x, y)| Position { x, y });
+        let renderer = Renderer::new(&grid).path(path, Colour::Green);
+        assert_eq!(renderer.render(false), "→→→\n↑#.\n↑..\n");
+    }
+
+    #[test]
+    fn moves() {
+        let grid = grid();
+        let renderer = Renderer::new(&grid).moves(
+            [
+                (Position { x: 0, y: 1 }, UP),
+                (Position { x: 2, y: 1 }, RIGHT),
+            ],
+            Colour::Green,
+        );
+        assert_eq!(renderer.render(false), "...\n↑#→\n...\n");
+    }
+
+    #[test]
+    fn ansi() {
+        let grid = Grid::parse("ab", Ok::<_, ()>).unwrap();
+        let renderer = Renderer::with_glyphs(&grid, |c| c.to_ascii_uppercase().to_string())
+            .colours(|p, _| (p.x == 0).then_some(Colour::Red))
+            .highlight([Position { x: 0, y: 0 }], Colour::Rgb([1, 2, 3]));
+        assert_eq!(renderer.render(true), "\x1b[31;48;2;1;2;3mA\x1b[0mB\n");
+    }
+}
@@ -0,0 +1,581 @@
+use std::{
+    cmp::Ordering,
+    collections::{hash_map::Entry, BinaryHeap, HashMap, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+use crate::{
+    grid::{BitGrid, Grid},
+    position::Position,
+};
+
+/// Outcome of a search: cost to reach every visited node and the node it was
+/// reached from.
+#[derive(Clone, Debug)]
+pub struct Paths<N, C> {
+    pub distances: HashMap<N, C>,
+    pub predecessors: HashMap<N, N>,
+}
+
+impl<N, C> Paths<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy,
+{
+    fn new(start: N, zero: C) -> Self {
+        Self {
+            distances: HashMap::from([(start, zero)]),
+            predecessors: HashMap::new(),
+        }
+    }
+
+    pub fn distance(&self, node: &N) -> Option<C> {
+        self.distances.get(node).copied()
+    }
+
+    /// Nodes from the start to `target` included, if `target` was reached.
+    pub fn path_to(&self, target: &N) -> Option<Vec<N>> {
+        self.distances.contains_key(target).then(|| {
+            let mut path = vec![target.clone()];
+            while let Some(previous) = self.predecessors.get(path.last().unwrap()) {
+                path.push(previous.clone());
+            }
+            path.reverse();
+            path
+        })
+    }
+}
+
+/// Breadth-first search from `start`, distances count the number of steps.
+pub fn bfs<N, F, I>(start: N, mut successors: F) -> Paths<N, usize>
+where
+    N: Clone + Eq + Hash,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    let mut paths = Paths::new(start.clone(), 0);
+    let mut queue = VecDeque::from([start]);
+    while let Some(node) = queue.pop_front() {
+        let distance = paths.distances[&node] + 1;
+        for next in successors(&node) {
+            if let Entry::Vacant(entry) = paths.distances.entry(next.clone()) {
+                entry.insert(distance);
+                paths.predecessors.insert(next.clone(), node.clone());
+                queue.push_back(next);
+            }
+        }
+    }
+    paths
+}
+
+/// Depth-first search from `start`.
+///
+/// Every reachable node is visited, distances are depths in the search tree and
+/// not the length of the shortest path.
+pub fn dfs<N, F, I>(start: N, mut successors: F) -> Paths<N, usize>
+where
+    N: Clone + Eq + Hash,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    let mut paths = Paths::new(start.clone(), 0);
+    let mut stack = vec![(start, None, 0)];
+    while let Some((node, previous, depth)) = stack.pop() {
+        if let Some(previous) = previous {
+            match paths.distances.entry(node.clone()) {
+                Entry::Occupied(_) => continue,
+                Entry::Vacant(entry) => {
+                    entry.insert(depth);
+                    paths.predecessors.insert(node.clone(), previous);
+                }
+            }
+        }
+        for next in successors(&node) {
+            if !paths.distances.contains_key(&next) {
+                stack.push((next, Some(node.clone()), depth + 1));
+            }
+        }
+    }
+    paths
+}
+
+/// Entry of a priority queue, ordered so that [`BinaryHeap`] pops the lowest
+/// priority first.
+struct Queued<N, C> {
+    priority: C,
+    node: N,
+}
+
+impl<N, C: Ord> PartialEq for Queued<N, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<N, C: Ord> Eq for Queued<N, C> {}
+
+impl<N, C: Ord> PartialOrd for Queued<N, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N, C: Ord> Ord for Queued<N, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+/// Dijkstra's shortest paths from `start` to every reachable node.
+///
+/// `successors` yields each neighbour with the cost of the step to reach it.
+/// `C::default()` is used as the zero cost.
+pub fn dijkstra<N, C, F, I>(start: N, mut successors: F) -> Paths<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut paths = Paths::new(start.clone(), C::default());
+    let mut queue = BinaryHeap::from([Queued {
+        priority: C::default(),
+        node: start,
+    }]);
+    while let Some(Queued { priority, node }) = queue.pop() {
+        if priority > paths.distances[&node] {
+            continue;
+        }
+        for (next, step) in successors(&node) {
+            let cost = priority + step;
+            if paths.distances.get(&next).is_none_or(|&best| cost < best) {
+                paths.distances.insert(next.clone(), cost);
+                paths.predecessors.insert(next.clone(), node.clone());
+                queue.push(Queued {
+                    priority: cost,
+                    node: next,
+                });
+            }
+        }
+    }
+    paths
+}
+
+/// A* search from `start` to the first node satisfying `is_goal`.
+///
+/// `heuristic` must never overestimate the remaining cost for the returned path to
+/// be the shortest one. Returns the path, both ends included, and its cost.
+pub fn astar<N, C, F, I, H, G>(
+    start: N,
+    mut successors: F,
+    mut heuristic: H,
+    mut is_goal: G,
+) -> Option<(Vec<N>, C)>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, C)>,
+    H: FnMut(&N) -> C,
+    G: FnMut(&N) -> bool,
+{
+    let mut paths = Paths::new(start.clone(), C::default());
+    let mut queue = BinaryHeap::from([Queued {
+        priority: heuristic(&start),
+        node: start,
+    }]);
+    while let Some(Queued { priority, node }) = queue.pop() {
+        let cost = paths.distances[&node];
+        if priority > cost + heuristic(&node) {
+            continue;
+        }
+        if is_goal(&node) {
+            return paths.path_to(&node).map(|path| (path, cost));
+        }
+        for (next, step) in successors(&node) {
+            let next_cost = cost + step;
+            if paths
+                .distances
+                .get(&next)
+                .is_none_or(|&best| next_cost < best)
+            {
+                paths.distances.insert(next.clone(), next_cost);
+                paths.predecessors.insert(next.clone(), node.clone());
+                queue.push(Queued {
+                    priority: next_cost + heuristic(&next),
+                    node: next,
+                });
+            }
+        }
+    }
+    None
+}
+
+/// Searches over orthogonal moves between the cells of a grid.
+impl<T> Grid<T> {
+    /// Breadth-first search from `start`, stepping from one cell to the next when
+    /// `passable(from, to)` holds.
+    pub fn bfs<P>(&self, start: Position, mut passable: P) -> Paths<Position, usize>
+    where
+        P: FnMut(&T, &T) -> bool,
+    {
+        bfs(start, |&p| {
+            let from = &self[&p];
+            self.neighbors4(p)
+                .filter(|(_, to)| passable(from, to))
+                .map(|(n, _)| n)
+                .collect::<Vec<_>>()
+        })
+    }
+
+    /// Depth-first search from `start`, stepping from one cell to the next when
+    /// `passable(from, to)` holds.
+    pub fn dfs<P>(&self, start: Position, mut passable: P) -> Paths<Position, usize>
+    where
+        P: FnMut(&T, &T) -> bool,
+    {
+        dfs(start, |&p| {
+            let from = &self[&p];
+            self.neighbors4(p)
+                .filter(|(_, to)| passable(from, to))
+                .map(|(n, _)| n)
+                .collect::<Vec<_>>()
+        })
+    }
+
+    /// Every cell reachable from `start`, itself included, stepping from one cell
+    /// to the next when `passable(from, to)` holds.
+    ///
+    /// Cheaper than [`Grid::bfs`] when neither distances nor paths are needed.
+    pub fn reachable<P>(&self, start: Position, mut passable: P) -> BitGrid
+    where
+        P: FnMut(&T, &T) -> bool,
+    {
+        let mut visited = BitGrid::new(self.width, self.height);
+        visited.insert(start);
+        let mut stack = vec![start];
+        while let Some(p) = stack.pop() {
+            let from = &self[&p];
+            for (n, to) in self.neighbors4(p) {
+                if passable(from, to) && visited.insert(n) {
+                    stack.push(n);
+                }
+            }
+        }
+        visited
+    }
+
+    /// Shortest paths from `start`, where `cost(from, to)` gives the cost of a step
+    /// or `None` when it is not allowed.
+    pub fn dijkstra<C, F>(&self, start: Position, mut cost: F) -> Paths<Position, C>
+    where
+        C: Copy + Ord + Add<Output = C> + Default,
+        F: FnMut(&T, &T) -> Option<C>,
+    {
+        dijkstra(start, |&p| {
+            let from = &self[&p];
+            self.neighbors4(p)
+                .filter_map(|(n, to)| Some((n, cost(from, to)?)))
+                .collect::<Vec<_>>()
+        })
+    }
+
+    /// Shortest path from `start` to `goal`, guided by the Manhattan distance.
+    ///
+    /// `cost(from, to)` gives the cost of a step or `None` when it is not allowed.
+    /// The heuristic assumes that every step costs at least 1.
+    pub fn astar<C, F>(
+        &self,
+        start: Position,
+        goal: Position,
+        mut cost: F,
+    ) -> Option<(Vec<Position>, C)>
+    where
+        C: Copy + Ord + Add<Output = C> + Default + TryFrom<usize>,
+        F: FnMut(&T, &T) -> Option<C>,
+    {
+        astar(
+            start,
+            |&p| {
+                let from = &self[&p];
+                self.neighbors4(p)
+                    .filter_map(|(n, to)| Some((n, cost(from, to)?)))
+                    .collect::<Vec<_>>()
+            },
+            |p| C::try_from(p.manhattan_distance(goal)).unwrap_or_default(),
+            |&p| p == goal,
+        )
+    }
+
+    /// Number of steps from the nearest of `sources` to every cell, stepping from
+    /// one cell to the next when `passable(from, to)` holds.
+    ///
+    /// Cells that cannot be reached are `None`.
+    pub fn distance_field<I, P>(&self, sources: I, mut passable: P) -> Grid<Option<u32>>
+    where
+        I: IntoIterator<Item = Position>,
+        P: FnMut(&T, &T) -> bool,
+    {
+        let mut field = Grid::filled(self.width, self.height, None);
+        let mut queue = VecDeque::new();
+        for source in sources {
+            if field[&source].is_none() {
+                field[&source] = Some(0);
+                queue.push_back(source);
+            }
+        }
+        while let Some(p) = queue.pop_front() {
+            let distance = field[&p].map(|d| d + 1);
+            for (n, to) in self.neighbors4(p) {
+                if field[&n].is_none() && passable(&self[&p], to) {
+                    field[&n] = distance;
+                    queue.push_back(n);
+                }
+            }
+        }
+        field
+    }
+
+    /// Lowest cost from the nearest of `sources` to every cell, where
+    /// `cost(from, to)` gives the cost of a step or `None` when it is not allowed.
+    ///
+    /// Cells that cannot be reached are `None`.
+    pub fn weighted_distance_field<I, F>(&self, sources: I, mut cost: F) -> Grid<Option<u32>>
+    where
+        I: IntoIterator<Item = Position>,
+        F: FnMut(&T, &T) -> Option<u32>,
+    {
+        let mut field = Grid::filled(self.width, self.height, None);
+        let mut queue = BinaryHeap::new();
+        for source in sources {
+            field[&source] = Some(0);
+            queue.push(Queued {
+                priority: 0,
+                node: source,
+            });
+        }
+        while let Some(Queued { priority, node }) = queue.pop() {
+            if field[&node].is_some_and(|best| priority > best) {
+                continue;
+            }
+            for (n, to) in self.neighbors4(node) {
+                let Some(step) = cost(&self[&node], to) else {
+                    continue;
+                };
+                let distance = priority + step;
+                if field[&n].is_none_or(|best| distance < best) {
+                    field[&n] = Some(distance);
+                    queue.push(Queued {
+                        priority: distance,
+                        node: n,
+                    });
+                }
+            }
+        }
+        field
+    }
+}
+
+/// Jump between two reachable cells shortening the path between two points.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Shortcut {
+    pub from: Position,
+    pub to: Position,
+    /// Difference between the shortest path and the path using the shortcut.
+    pub saving: u32,
+}
+
+/// Every jump of at most `max_jump` steps, measured with the Manhattan distance,
+/// that shortens the path between a start and an end.
+///
+/// `from_start` and `to_end` are distance fields from the start and from the end.
+/// A jump from `from` to `to` costs its length, it is kept if
+/// `from_start[from] + jump + to_end[to]` is lower than the shortest path.
+pub fn shortcuts(
+    from_start: &Grid<Option<u32>>,
+    to_end: &Grid<Option<u32>>,
+    max_jump: usize,
+) -> Vec<Shortcut> {
+    let Some(best) = from_start
+        .zip_with(to_end, |a, b| Some((*a)? + (*b)?))
+        .iter()
+        .flatten()
+        .min()
+        .copied()
+    else {
+        return Vec::new();
+    };
+    let mut out = Vec::new();
+    for (from, start) in from_start.positions() {
+        let Some(start) = *start else { continue };
+        let min_y = from.y.saturating_sub(max_jump);
+        let max_y = (from.y + max_jump).min(to_end.height - 1);
+        for y in min_y..=max_y {
+            let spare = max_jump - from.y.abs_diff(y);
+            let min_x = from.x.saturating_sub(spare);
+            let max_x = (from.x + spare).min(to_end.width - 1);
+            for x in min_x..=max_x {
+                let to = Position { x, y };
+                let Some(end) = to_end[&to] else { continue };
+                let jump = from.manhattan_distance(to) as u32;
+                let length = start + jump + end;
+                if length < best {
+                    out.push(Shortcut {
+                        from,
+                        to,
+                        saving: best - length,
+                    });
+                }
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use test_case::test_case;
+
+    const MAZE: &str = "S.#.
+.##.
+....
+#.#E";
+
+    fn maze() -> Grid<char> {
+        Grid::parse(MAZE, Ok::<_, ()>).unwrap()
+    }
+
+    fn open(_: &char, to: &char) -> bool {
+        *to != '#'
+    }
+
+    const START: Position = Position { x: 0, y: 0 };
+    const END: Position = Position { x: 3, y: 3 };
+
+    #[test]
+    fn bfs_distances() {
+        let paths = maze().bfs(START, open);
+        assert_eq!(paths.distance(&END), Some(6));
+        assert_eq!(paths.distance(&Position { x: 3, y: 0 }), Some(7));
+        assert_eq!(paths.distance(&Position { x: 2, y: 0 }), None);
+        assert_eq!(paths.distances.len(), 11);
+    }
+
+    #[test]
+    fn bfs_path() {
+        let paths = maze().bfs(START, open);
+        let path = paths.path_to(&END).unwrap();
+        assert_eq!(path.len(), 7);
+        assert_eq!(path.first(), Some(&START));
+        assert_eq!(path.last(), Some(&END));
+        assert!(path.windows(2).all(|w| w[0].manhattan_distance(w[1]) == 1));
+        assert_eq!(paths.path_to(&Position { x: 2, y: 0 }), None);
+    }
+
+    #[test]
+    fn dfs_reachable() {
+        let paths = maze().dfs(START, open);
+        assert_eq!(paths.distances.len(), 11);
+        assert!(paths.path_to(&END).is_some());
+    }
+
+    #[test]
+    fn reachable_cells() {
+        let grid = maze();
+        let reachable = grid.reachable(START, open);
+        assert_eq!(reachable.count(), 11);
+        let bfs: HashSet<_> = grid.bfs(START, open).distances.into_keys().collect();
+        assert_eq!(reachable.iter().collect::<HashSet<_>>(), bfs);
+    }
+
+    #[test]
+    fn dijkstra_weighted() {
+        let grid = Grid::parse("191\n111", |c| c.to_digit(10).ok_or(c)).unwrap();
+        let end = Position { x: 2, y: 0 };
+        let paths = grid.dijkstra(START, |_, &to| Some(to));
+        assert_eq!(paths.distance(&end), Some(4));
+        assert_eq!(paths.path_to(&end).map(|p| p.len()), Some(5));
+        let (path, cost) = grid.astar(START, end, |_, &to| Some(to)).unwrap();
+        assert_eq!((path.len(), cost), (5, 4));
+    }
+
+    #[test]
+    fn astar_shortest() {
+        let grid = maze();
+        let (path, cost) = grid
+            .astar(START, END, |_, to| (*to != '#').then_some(1u32))
+            .unwrap();
+        assert_eq!(cost, 6);
+        assert_eq!(path.len(), 7);
+        let unreachable = Position { x: 2, y: 0 };
+        assert_eq!(
+            grid.astar(START, unreachable, |_, to| (*to != '#').then_some(1u32)),
+            None
+        );
+    }
+
+    #[test]
+    fn generic_graph() {
+        // Integers linked by doubling or incrementing, the shortest path from 1 to
+        // 10 is 1 -> 2 -> 4 -> 5 -> 10.
+        let paths = bfs(1u32, |&n| [n * 2, n + 1].into_iter().filter(|&n| n <= 10));
+        assert_eq!(paths.path_to(&10), Some(vec![1, 2, 4, 5, 10]));
+        let found = astar(1u32, |&n| [(n * 2, 1u32), (n + 1, 1)], |_| 0, |&n| n == 10);
+        assert_eq!(found.map(|(_, cost)| cost), Some(4));
+    }
+
+    const RACE: &str = "###############
+#...#...#.....#
+#.#.#.#.#.###.#
+#S#...#.#.#...#
+#######.#.#.###
+#######.#.#...#
+#######.#.###.#
+###..E#...#...#
+###.#######.###
+#...###...#...#
+#.#####.#.###.#
+#.#...#.#.#...#
+#.#.#.#.#.#.###
+#...#...#...###
+###############";
+
+    #[test]
+    fn distance_field() {
+        let grid = maze();
+        let field = grid.distance_field([START, END], open);
+        assert_eq!(field[&START], Some(0));
+        assert_eq!(field[&Position { x: 0, y: 2 }], Some(2));
+        assert_eq!(field[&Position { x: 3, y: 2 }], Some(1));
+        assert_eq!(field[&Position { x: 3, y: 0 }], Some(3));
+        assert_eq!(field[&Position { x: 2, y: 0 }], None);
+    }
+
+    #[test]
+    fn weighted_distance_field() {
+        let grid = Grid::parse("191\n111", |c| c.to_digit(10).ok_or(c)).unwrap();
+        let field = grid.weighted_distance_field([START], |_, &to| Some(to));
+        let rendered = field.map(|d| d.unwrap()).to_string();
+        assert_eq!(rendered, "094\n123\n");
+    }
+
+    #[test_case(2, 1 => 44)]
+    #[test_case(2, 64 => 1)]
+    #[test_case(20, 50 => 285)]
+    #[test_case(20, 76 => 3)]
+    fn race_shortcuts(max_jump: usize, min_saving: u32) -> usize {
+        let grid = Grid::parse(RACE, Ok::<_, ()>).unwrap();
+        let start = grid.find(|&c| c == 'S').unwrap();
+        let end = grid.find(|&c| c == 'E').unwrap();
+        let from_start = grid.distance_field([start], open);
+        let to_end = grid.distance_field([end], open);
+        assert_eq!(from_start[&end], Some(84));
+        shortcuts(&from_start, &to_end, max_jump)
+            .iter()
+            .filter(|s| s.saving >= min_saving)
+            .count()
+    }
+}
@@ -1,83 +1,64 @@
 use aoc_runner_derive::{aoc, aoc_generator};
-use ndarray::{Array2, ArrayView1, ArrayView2};
+use utils::{grid::Grid, pattern::Pattern, position::Position};
 
-type Puzzle = Array2<u8>;
+type Puzzle = Grid<u8>;
 
 #[aoc_generator(day4)]
 pub fn input_generator(input: &str) -> Puzzle {
-    let bfr: Vec<Vec<u8>> = input
-        .lines()
-        .map(|l| l.bytes().collect::<Vec<u8>>())
-        .collect::<Vec<_>>();
-    Array2::from_shape_vec(
-        (bfr.len(), bfr[0].len()),
-        bfr.iter().flatten().copied().collect(),
-    )
-    .unwrap()
+    Grid::parse(input, u8::try_from).expect("Failed to parse puzzle")
 }
 
 const XMAS: u64 = 0x584d4153;
 const SAMX: u64 = 0x53414d58;
-const MAS: u64 = 0x004d4153;
-const SAM: u64 = 0x0053414d;
 
-fn bfr_filter(bfr: &ArrayView1<u8>) -> bool {
-    let v: u64 = bfr.iter().fold(0, |acc, elem| acc << 8 | *elem as u64);
+fn bfr_filter<'a>(bfr: impl Iterator<Item = &'a u8>) -> bool {
+    let v: u64 = bfr.fold(0, |acc, elem| acc << 8 | *elem as u64);
     v == XMAS || v == SAMX
 }
 
-fn count_lines(grid: &ArrayView2<u8>) -> usize {
-    grid.windows((1, 4))
-        .into_iter()
-        .filter(|arr| bfr_filter(&arr.flatten().view()))
+fn count_lines(grid: &Grid<u8>) -> usize {
+    grid.windows(4, 1)
+        .filter(|window| bfr_filter(window.iter()))
         .count()
 }
 
-fn count_columns(grid: &ArrayView2<u8>) -> usize {
-    grid.windows((4, 1))
-        .into_iter()
-        .filter(|arr| bfr_filter(&arr.flatten().view()))
+fn count_columns(grid: &Grid<u8>) -> usize {
+    grid.windows(1, 4)
+        .filter(|window| bfr_filter(window.iter()))
         .count()
 }
 
-fn count_diagonals(grid: &ArrayView2<u8>) -> usize {
-    grid.windows((4, 4))
-        .into_iter()
+fn count_diagonals(grid: &Grid<u8>) -> usize {
+    grid.windows(4, 4)
         .map(|grid| {
-            let d1 = (0..4).fold(0, |acc, i| acc << 8 | grid[[i, i]] as u64);
+            let d1 = (0..4).fold(0, |acc, i| acc << 8 | grid[&Position { x: i, y: i }] as u64);
             let diag1 = d1 == XMAS || d1 == SAMX;
-            let d2 = (0..4).fold(0, |acc, i| acc << 8 | grid[[i, 3 - i]] as u64);
+            let d2 = (0..4).fold(0, |acc, i| {
+                acc << 8 | grid[&Position { x: 3 - i, y: i }] as u64
+            });
             let diag2 = d2 == XMAS || d2 == SAMX;
             diag1 as usize + diag2 as usize
         })
         .sum()
 }
 
-fn check_xmas_grid(grid: &ArrayView2<u8>) -> usize {
+fn check_xmas_grid(grid: &Grid<u8>) -> usize {
     count_lines(grid) + count_columns(grid) + count_diagonals(grid)
 }
 
 #[aoc(day4, part1)]
 pub fn solve_part1(input: &Puzzle) -> usize {
-    check_xmas_grid(&input.view())
+    check_xmas_grid(input)
 }
 
-fn find_xmas(grid: &ArrayView2<u8>) -> usize {
-    grid.windows((3, 3))
-        .into_iter()
-        .filter(|grid| {
-            let d1 = (0..3).fold(0, |acc, i| acc << 8 | grid[[i, i]] as u64);
-            let diag1 = d1 == MAS || d1 == SAM;
-            let d2 = (0..3).fold(0, |acc, i| acc << 8 | grid[[i, 2 - i]] as u64);
-            let diag2 = d2 == MAS || d2 == SAM;
-            diag1 && diag2
-        })
-        .count()
+fn find_xmas(grid: &Grid<u8>) -> usize {
+    let cross = Pattern::parse("M.S\n.A.\nM.S", '.').expect("Invalid pattern");
+    cross.find_all(grid, true).len()
 }
 
 #[aoc(day4, part2)]
 pub fn solve_part2(input: &Puzzle) -> usize {
-    find_xmas(&input.view())
+    find_xmas(input)
 }
 
 #[cfg(test)]
@@ -148,7 +129,7 @@ M.S"#;
     #[test_case(TEST5X4 => 5)]
     fn part1_lines(input: &str) -> usize {
         let grid = input_generator(input);
-        count_lines(&grid.view())
+        count_lines(&grid)
     }
 
     #[test_case(TEST4X4 => 0)]
@@ -157,7 +138,7 @@ M.S"#;
     #[test_case(TESTV5X4 => 5)]
     fn part1_columns(input: &str) -> usize {
         let grid = input_generator(input);
-        count_columns(&grid.view())
+        count_columns(&grid)
     }
 
     #[test_case(TEST4X4 => 2)]
@@ -168,7 +149,7 @@ M.S"#;
     #[test_case(TESTR4X4 => 1)]
     fn part1_diagonals(input: &str) -> usize {
         let grid = input_generator(input);
-        count_diagonals(&grid.view())
+        count_diagonals(&grid)
     }
 
     #[test_case(TEST => 9)]
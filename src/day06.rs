@@ -1,14 +1,6 @@
-use std::collections::HashSet;
-
 use aoc_runner_derive::{aoc, aoc_generator};
-use nom::branch::alt;
-use nom::bytes::complete::tag;
-use nom::character::complete::newline;
-use nom::combinator::map;
-use nom::multi::{many1, separated_list1};
-use nom::IResult;
-use utils::grid::Grid;
-use utils::movement::{Movement, CLOCKWISE, DOWN, LEFT, RIGHT, UP};
+use utils::grid::{BitGrid, DirectionBitGrid, Grid};
+use utils::movement::Direction;
 use utils::position::Position;
 
 type Puzzle = Grid<Tile>;
@@ -20,66 +12,49 @@ pub enum Tile {
     Wall,
 }
 
-fn guard(input: &str) -> IResult<&str, Tile> {
-    map(tag("^"), |_| Tile::Guard)(input)
-}
-
-fn floor(input: &str) -> IResult<&str, Tile> {
-    map(tag("."), |_| Tile::Floor)(input)
-}
-
-fn wall(input: &str) -> IResult<&str, Tile> {
-    map(tag("#"), |_| Tile::Wall)(input)
-}
+impl TryFrom<char> for Tile {
+    type Error = char;
 
-fn tile(input: &str) -> IResult<&str, Tile> {
-    alt((guard, floor, wall))(input)
-}
-
-fn row(input: &str) -> IResult<&str, Vec<Tile>> {
-    many1(tile)(input)
-}
-
-fn puzzle(input: &str) -> IResult<&str, Puzzle> {
-    map(separated_list1(newline, row), Grid::from_vec)(input)
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '^' => Ok(Tile::Guard),
+            '.' => Ok(Tile::Floor),
+            '#' => Ok(Tile::Wall),
+            other => Err(other),
+        }
+    }
 }
 
 #[aoc_generator(day6)]
 pub fn input_generator(input: &str) -> Puzzle {
-    puzzle(input).unwrap().1
+    Grid::parse(input, Tile::try_from).expect("Failed to parse puzzle")
 }
 
 #[aoc(day6, part1)]
 pub fn solve_part1(input: &Puzzle) -> u64 {
-    get_visited(input)
-        .into_iter()
-        .map(|(p, _)| p)
-        .collect::<HashSet<_>>()
-        .len() as u64
+    let mut visited = BitGrid::new(input.width, input.height);
+    visited.extend(get_visited(input).into_iter().map(|(p, _)| p));
+    visited.count() as u64
 }
 
-fn get_visited(input: &Puzzle) -> Vec<(Position, Movement)> {
+fn get_visited(input: &Puzzle) -> Vec<(Position, Direction)> {
     let mut position = input
-        .positions()
-        .filter(|&(_, t)| *t == Tile::Guard)
-        .map(|(p, _)| p)
-        .next()
+        .find(|&t| t == Tile::Guard)
         .expect("Failed to find guard");
     let mut visited = Vec::new();
-    let mut moves = CLOCKWISE.iter().cycle();
-    let mut movement = moves.next().unwrap();
-    visited.push((position, *movement));
-    while let Some(next_pos) = input.checked_move(position, *movement) {
+    let mut direction = Direction::North;
+    visited.push((position, direction));
+    while let Some(next_pos) = input.checked_move(position, direction.into()) {
         let next_tile = input[&next_pos];
         match next_tile {
             Tile::Wall => {
-                movement = moves.next().unwrap();
+                direction = direction.turn_right();
             }
             _ => {
-                position = position + movement;
+                position = next_pos;
             }
         }
-        visited.push((position, *movement));
+        visited.push((position, direction));
     }
     visited
 }
@@ -87,20 +62,18 @@ fn get_visited(input: &Puzzle) -> Vec<(Position, Movement)> {
 #[aoc(day6, part2)]
 pub fn solve_part2(input: &Puzzle) -> u64 {
     let mut counter = 0;
-    let mut visited: HashSet<Position> = HashSet::new();
+    let mut visited = BitGrid::new(input.width, input.height);
+    let mut bumps = DirectionBitGrid::new(input.width, input.height);
     let mut curr_pos = input
-        .positions()
-        .filter(|&(_, t)| *t == Tile::Guard)
-        .map(|(p, _)| p)
-        .next()
+        .find(|&t| t == Tile::Guard)
         .expect("Failed to find guard");
-    let mut curr_move = UP;
-    while let Some(pos) = input.checked_move(curr_pos, curr_move) {
+    let mut curr_dir = Direction::North;
+    while let Some(pos) = input.checked_move(curr_pos, curr_dir.into()) {
         visited.insert(curr_pos);
         if input[&pos] == Tile::Wall {
-            curr_move = rotate(curr_move);
+            curr_dir = curr_dir.turn_right();
         } else {
-            if !visited.contains(&pos) && is_loop(input, curr_pos, curr_move) {
+            if !visited.contains(pos) && is_loop(input, curr_pos, curr_dir, &mut bumps) {
                 counter += 1;
             }
             curr_pos = pos;
@@ -109,43 +82,30 @@ pub fn solve_part2(input: &Puzzle) -> u64 {
     counter
 }
 
-fn rotate(m: Movement) -> Movement {
-    if m == UP {
-        return RIGHT;
-    }
-    if m == RIGHT {
-        return DOWN;
-    }
-    if m == DOWN {
-        return LEFT;
-    }
-    if m == LEFT {
-        return UP;
-    }
-    unreachable!()
-}
-
-fn is_loop(grid: &Grid<Tile>, init: Position, movement: Movement) -> bool {
+fn is_loop(
+    grid: &Grid<Tile>,
+    init: Position,
+    direction: Direction,
+    visited: &mut DirectionBitGrid,
+) -> bool {
     let block = match grid
-        .checked_move(init, movement)
+        .checked_move(init, direction.into())
         .filter(|p| grid[p] == Tile::Floor)
     {
         Some(pos) => pos,
         None => return false,
     };
-    let mut visited: HashSet<(Position, Movement)> = HashSet::new();
+    visited.clear();
     let mut curr_pos = init;
-    let mut curr_move = movement;
-    while let Some(pos) = grid.checked_move(curr_pos, curr_move) {
-        if pos == block || grid[&pos] == Tile::Wall {
-            if visited.contains(&(pos, curr_move)) {
-                return true;
-            }
-            visited.insert((pos, curr_move));
-            curr_move = rotate(curr_move);
-        } else {
-            curr_pos = pos;
+    let mut curr_dir = direction;
+    while let Some((pos, steps)) = grid.cast_until(curr_pos, curr_dir.into(), |p, &t| {
+        p == block || t == Tile::Wall
+    }) {
+        if !visited.insert(pos, curr_dir) {
+            return true;
         }
+        curr_pos = curr_pos + curr_dir.movement() * (steps as isize - 1);
+        curr_dir = curr_dir.turn_right();
     }
     false
 }
@@ -7,7 +7,7 @@ use nom::{
     IResult,
 };
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
-use utils::math::asm_div_rem;
+use utils::math::{div_rem, Divider};
 
 type Puzzle = Vec<(u64, Vec<u64>)>;
 
@@ -28,12 +28,9 @@ fn valid_equation(result: u64, numbers: &[u64]) -> bool {
         [] => false,
         [last] => *last == result,
         [head @ .., tail] => {
-            let (quotient, remainder) = unsafe {
-                // SAFETY: input does not contain 0, so YOLO
-                asm_div_rem(result, *tail)
-            };
-            remainder == 0 && valid_equation(quotient, head)
-                || result > *tail && valid_equation(result - tail, head)
+            div_rem(result, *tail).is_some_and(|(quotient, remainder)| {
+                remainder == 0 && valid_equation(quotient, head)
+            }) || result > *tail && valid_equation(result - tail, head)
         }
     }
 }
@@ -52,17 +49,11 @@ fn valid_equation_concat(result: u64, numbers: &[u64]) -> bool {
         [] => unreachable!(),
         [last] => *last == result,
         [head @ .., last] => {
-            let (quotient, remainder) = unsafe {
-                // SAFETY: input does not contain 0, so YOLO
-                asm_div_rem(result, *last)
-            };
-            let split = 10u64.pow(last.ilog10() + 1);
-            let (prefix, suffix) = unsafe {
-                // SAFETY: m is a power of 10 greater or equal to 10^1
-                asm_div_rem(result, split)
-            };
-            remainder == 0 && valid_equation_concat(quotient, head)
-                || result > *last && valid_equation_concat(result - last, head)
+            let digits = last.checked_ilog10().map_or(1, |log| log + 1);
+            let (prefix, suffix) = Divider::power_of_ten(digits).div_rem(result);
+            div_rem(result, *last).is_some_and(|(quotient, remainder)| {
+                remainder == 0 && valid_equation_concat(quotient, head)
+            }) || result > *last && valid_equation_concat(result - last, head)
                 || suffix == *last && valid_equation_concat(prefix, head)
         }
     }
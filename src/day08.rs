@@ -4,11 +4,10 @@ use std::{
 };
 
 use aoc_runner_derive::{aoc, aoc_generator};
-use utils::{movement::Movement, position::Position};
+use utils::{movement::Movement, point::Point, position::Position, rect::Rect};
 
 pub struct Puzzle {
-    height: usize,
-    width: usize,
+    bounds: Rect,
     data: HashMap<char, Vec<Position>>,
 }
 
@@ -27,17 +26,17 @@ pub fn input_generator(input: &str) -> Puzzle {
         }
     }
     Puzzle {
-        height,
-        width,
+        bounds: Rect::sized(width, height),
         data,
     }
 }
 
 fn antinode(antenna: Position, other: Position, puzzle: &Puzzle) -> Option<Position> {
-    let m = Movement::between(antenna, other)?;
-    other
-        .checked_move(m)
-        .filter(|&Position { x, y }| x < puzzle.width && y < puzzle.height)
+    let antenna = Point::try_from(antenna).ok()?;
+    let other = Point::try_from(other).ok()?;
+    Position::try_from(other * 2 - antenna)
+        .ok()
+        .filter(|&p| puzzle.bounds.contains(p))
 }
 
 fn antinodes(antennas: &[Position], puzzle: &Puzzle) -> HashSet<Position> {
@@ -64,10 +63,10 @@ pub fn solve_part1(input: &Puzzle) -> usize {
 fn antinode_extended(antenna: Position, other: Position, puzzle: &Puzzle) -> HashSet<Position> {
     let mut out = HashSet::new();
     let mut current = antenna;
-    let m = Movement::between(antenna, other).unwrap();
+    let m = Movement::between(antenna, other).unwrap().primitive();
     while let Some(valid) = current
         .checked_move(m)
-        .filter(|&Position { x, y }| x < puzzle.width && y < puzzle.height)
+        .filter(|&p| puzzle.bounds.contains(p))
     {
         out.insert(valid);
         current = valid;
@@ -149,6 +148,12 @@ mod tests {
 ..........
 .........."#;
 
+    const STEEP: &str = r#"a....
+.....
+.....
+.....
+..a.."#;
+
     #[test_case(TEST => 14)]
     #[test_case(SIMPLER => 2)]
     #[test_case(THREE => 4)]
@@ -158,6 +163,7 @@ mod tests {
 
     #[test_case(TEST => 34)]
     #[test_case(T_TEST => 9)]
+    #[test_case(STEEP => 3)]
     fn part2(input: &str) -> usize {
         solve_part2(&input_generator(input))
     }
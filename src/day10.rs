@@ -1,69 +1,44 @@
-use std::collections::HashSet;
-
 use aoc_runner_derive::{aoc, aoc_generator};
-use utils::{grid::Grid, movement::CLOCKWISE, position::Position};
+use rayon::iter::{ParallelBridge, ParallelIterator};
+use utils::{grid::Grid, position::Position};
 
 type Puzzle = Grid<u32>;
 
 #[aoc_generator(day10)]
 pub fn input_generator(input: &str) -> Puzzle {
-    Grid::from_vec(
-        input
-            .lines()
-            .map(|line| {
-                line.chars()
-                    .flat_map(|chr| match chr {
-                        '.' => Some(u32::MAX),
-                        chr => chr.to_digit(10),
-                    })
-                    .collect()
-            })
-            .collect(),
-    )
+    Grid::parse(input, |chr| match chr {
+        '.' => Ok(u32::MAX),
+        chr => chr.to_digit(10).ok_or(chr),
+    })
+    .expect("Failed to parse puzzle")
 }
 
-fn score_from(position: Position, elevation: u32, grid: &Grid<u32>) -> HashSet<Position> {
-    if elevation == 9 {
-        return HashSet::from([position]);
-    }
-    CLOCKWISE
+fn score_from(position: Position, grid: &Grid<u32>) -> usize {
+    grid.reachable(position, |&from, &to| to == from + 1)
         .iter()
-        .flat_map(|m| grid.checked_move(position, *m))
-        .filter(|n| grid[n] == elevation + 1)
-        .fold(HashSet::new(), |mut h, n| {
-            h.extend(score_from(n, grid[&n], grid));
-            h
-        })
+        .filter(|n| grid[n] == 9)
+        .count()
 }
 
 #[aoc(day10, part1)]
 pub fn solve_part1(input: &Puzzle) -> usize {
-    let trailheads = input
-        .positions()
-        .filter(|(_, &h)| h == 0)
-        .map(|(p, h)| (p, *h));
-    trailheads.map(|(p, h)| score_from(p, h, input).len()).sum()
+    let trailheads = input.find_all(|&h| h == 0).par_bridge();
+    trailheads.map(|p| score_from(p, input)).sum()
 }
 
 fn count_path(position: Position, elevation: u32, grid: &Grid<u32>) -> usize {
     if elevation == 9 {
         return 1;
     }
-    CLOCKWISE
-        .iter()
-        .flat_map(|m| grid.checked_move(position, *m))
-        .filter(|n| grid[n] == elevation + 1)
-        .map(|n| count_path(n, grid[&n], grid))
+    grid.neighbors4_where(position, |&h| h == elevation + 1)
+        .map(|(n, &e)| count_path(n, e, grid))
         .sum()
 }
 
 #[aoc(day10, part2)]
 pub fn solve_part2(input: &Puzzle) -> usize {
-    let trailheads = input
-        .positions()
-        .filter(|(_, &h)| h == 0)
-        .map(|(p, h)| (p, *h));
-    trailheads.map(|(p, h)| count_path(p, h, input)).sum()
+    let trailheads = input.find_all(|&h| h == 0).par_bridge();
+    trailheads.map(|p| count_path(p, 0, input)).sum()
 }
 
 #[cfg(test)]
@@ -126,9 +101,7 @@ mod tests {
     fn part1_scores(input: &str) -> Vec<usize> {
         let puzzle = input_generator(input);
         let trailheads = puzzle.positions().filter(|(_, &h)| h == 0);
-        let out: Vec<usize> = trailheads
-            .map(|(p, &h)| score_from(p, h, &puzzle).len())
-            .collect();
+        let out: Vec<usize> = trailheads.map(|(p, _)| score_from(p, &puzzle)).collect();
         out
     }
 
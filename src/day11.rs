@@ -1,6 +1,7 @@
 use std::{collections::HashMap, num::ParseIntError, str::FromStr};
 
 use aoc_runner_derive::{aoc, aoc_generator};
+use utils::math::Divider;
 
 pub struct Puzzle(Vec<u64>);
 
@@ -39,13 +40,15 @@ fn stone_count(stone: u64, blink_left: u64, cache: &mut HashMap<(u64, u64), u64>
                 1
             } else if stone == 0 {
                 stone_count(1, blink_left - 1, cache)
-            } else if (stone.ilog10() + 1) % 2 == 0 {
-                let split = 10u64.pow((stone.ilog10() + 1) / 2);
-                let left = stone_count(stone / split, blink_left - 1, cache);
-                let right = stone_count(stone % split, blink_left - 1, cache);
-                left + right
             } else {
-                stone_count(stone * 2024, blink_left - 1, cache)
+                let digits = stone.ilog10() + 1;
+                if digits.is_multiple_of(2) {
+                    let (left, right) = Divider::power_of_ten(digits / 2).div_rem(stone);
+                    stone_count(left, blink_left - 1, cache)
+                        + stone_count(right, blink_left - 1, cache)
+                } else {
+                    stone_count(stone * 2024, blink_left - 1, cache)
+                }
             };
             cache.insert((stone, blink_left), count);
             count
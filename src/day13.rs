@@ -2,12 +2,12 @@ use aoc_runner_derive::{aoc, aoc_generator};
 use ndarray::{array, Axis};
 use nom::{
     bytes::complete::tag,
-    character::complete::{anychar, newline, u32},
-    combinator::{map, opt},
-    multi::separated_list1,
+    character::complete::{anychar, newline},
+    combinator::map,
     sequence::{preceded, separated_pair, terminated},
     IResult,
 };
+use utils::parse::{blocks, unsigned_pair};
 
 #[derive(Debug)]
 pub struct V2 {
@@ -30,24 +30,17 @@ pub struct Machine {
 #[derive(Debug)]
 pub struct Puzzle(Vec<Machine>);
 
-fn movement(input: &str) -> IResult<&str, V2> {
-    map(
-        separated_pair(
-            preceded(tag("X+"), u32),
-            tag(", "),
-            preceded(tag("Y+"), u32),
-        ),
-        |(x, y)| V2 {
-            x: x as f64,
-            y: y as f64,
-        },
-    )(input)
+fn v2(pair: (usize, usize)) -> V2 {
+    V2 {
+        x: pair.0 as f64,
+        y: pair.1 as f64,
+    }
 }
 
 fn button(input: &str) -> IResult<&str, V2> {
     preceded(
         terminated(preceded(tag("Button "), anychar), tag(": ")),
-        movement,
+        map(unsigned_pair("X+", ", Y+"), v2),
     )(input)
 }
 
@@ -58,22 +51,8 @@ fn buttons(input: &str) -> IResult<&str, Buttons> {
     })(input)
 }
 
-fn position(input: &str) -> IResult<&str, V2> {
-    map(
-        separated_pair(
-            preceded(tag("X="), u32),
-            tag(", "),
-            preceded(tag("Y="), u32),
-        ),
-        |(x, y)| V2 {
-            x: x as f64,
-            y: y as f64,
-        },
-    )(input)
-}
-
 fn prize(input: &str) -> IResult<&str, V2> {
-    preceded(tag("Prize: "), position)(input)
+    preceded(tag("Prize: "), map(unsigned_pair("X=", ", Y="), v2))(input)
 }
 
 fn machine(input: &str) -> IResult<&str, Machine> {
@@ -84,10 +63,7 @@ fn machine(input: &str) -> IResult<&str, Machine> {
 }
 
 fn puzzle(input: &str) -> IResult<&str, Puzzle> {
-    map(
-        separated_list1(newline, terminated(machine, opt(newline))),
-        Puzzle,
-    )(input)
+    map(blocks(machine), Puzzle)(input)
 }
 
 #[aoc_generator(day13)]
@@ -184,6 +160,13 @@ Button A: X+69, Y+23
 Button B: X+27, Y+71
 Prize: X=18641, Y=10279";
 
+    #[test_case("Button A: X+94, Y+34" => true)]
+    #[test_case("Button A: X94, Y-34" => false)]
+    #[test_case("Button B: X+22, Y-67" => false)]
+    fn button_offsets(input: &str) -> bool {
+        button(input).is_ok()
+    }
+
     #[test_case(TEST => 480.)]
     fn part1(input: &str) -> f64 {
         solve_part1(&input_generator(input))
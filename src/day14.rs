@@ -1,15 +1,15 @@
-use std::collections::HashSet;
-
 use aoc_runner_derive::{aoc, aoc_generator};
 use nom::{
-    bytes::complete::tag,
-    character::complete::{i64, newline, u64},
-    combinator::map,
-    multi::separated_list1,
-    sequence::{preceded, separated_pair},
-    IResult,
+    bytes::complete::tag, character::complete::newline, combinator::map, multi::separated_list1,
+    sequence::separated_pair, IResult,
+};
+use utils::{
+    grid::{BitGrid, TorusGrid},
+    movement::Movement,
+    parse::{movement, position},
+    position::Position,
+    rect::Rect,
 };
-use utils::{movement::Movement, position::Position};
 
 #[derive(Debug)]
 pub struct Puzzle(Vec<(Position, Movement)>);
@@ -24,27 +24,7 @@ fn puzzle(input: &str) -> IResult<&str, Puzzle> {
 }
 
 fn robot(input: &str) -> IResult<&str, (Position, Movement)> {
-    separated_pair(position, tag(" "), movement)(input)
-}
-
-fn position(input: &str) -> IResult<&str, Position> {
-    map(
-        preceded(tag("p="), separated_pair(u64, tag(","), u64)),
-        |(x, y)| Position {
-            x: x as usize,
-            y: y as usize,
-        },
-    )(input)
-}
-
-fn movement(input: &str) -> IResult<&str, Movement> {
-    map(
-        preceded(tag("v="), separated_pair(i64, tag(","), i64)),
-        |(x, y)| Movement {
-            x: x as isize,
-            y: y as isize,
-        },
-    )(input)
+    separated_pair(position("p=", ","), tag(" "), movement("v=", ","))(input)
 }
 
 #[aoc(day14, part1)]
@@ -63,25 +43,16 @@ pub fn solve_part1_dim(input: &Puzzle, seconds: usize, width: usize, height: usi
 pub struct Quadrant(usize, usize, usize, usize);
 
 pub fn check_quadrant(robots: &[(Position, Movement)], width: usize, height: usize) -> Quadrant {
-    let mut out = Quadrant::default();
-    let w = width / 2;
-    let h = height / 2;
-    for robot in robots {
-        match (robot.0.x.cmp(&w), robot.0.y.cmp(&h)) {
-            (std::cmp::Ordering::Less, std::cmp::Ordering::Less) => out.0 += 1,
-            (std::cmp::Ordering::Less, std::cmp::Ordering::Greater) => out.1 += 1,
-            (std::cmp::Ordering::Greater, std::cmp::Ordering::Less) => out.2 += 1,
-            (std::cmp::Ordering::Greater, std::cmp::Ordering::Greater) => out.3 += 1,
-            _ => (),
-        }
-    }
-    out
+    let [top_left, top_right, bottom_left, bottom_right] = Rect::sized(width, height)
+        .split_into_quadrants()
+        .map(|q| robots.iter().filter(|(p, _)| q.contains(*p)).count());
+    Quadrant(top_left, bottom_left, top_right, bottom_right)
 }
 
 #[aoc(day14, part2)]
 pub fn solve_part2(input: &Puzzle) -> usize {
     let mut robots = input.0.clone();
-    let mut bfr = HashSet::with_capacity(robots.len());
+    let mut bfr = BitGrid::new(101, 103);
     let mut seconds = 0;
     while !check_xmas_tree(&robots, &mut bfr) {
         seconds += 1;
@@ -91,27 +62,43 @@ pub fn solve_part2(input: &Puzzle) -> usize {
 }
 
 pub fn move_bots(robots: &mut [(Position, Movement)], steps: usize, width: usize, height: usize) {
-    let iwidth = width as isize;
-    let iheight = height as isize;
-    let isecs = steps as isize;
+    let field = TorusGrid::filled(width, height, ());
     for robot in robots {
-        robot.0.x = (robot.0.x as isize + robot.1.x * isecs).rem_euclid(iwidth) as usize;
-        robot.0.y = (robot.0.y as isize + robot.1.y * isecs).rem_euclid(iheight) as usize;
+        robot.0 = field.wrapping_move_n(robot.0, robot.1, steps);
     }
 }
 
 // Puzzle was construted backward, robots were positionned to form a x-mas tree.
 // Robots were added to other unique locations then negative movement was applied
-pub fn check_xmas_tree(robots: &[(Position, Movement)], bfr: &mut HashSet<Position>) -> bool {
+pub fn check_xmas_tree(robots: &[(Position, Movement)], bfr: &mut BitGrid) -> bool {
     bfr.clear();
-    bfr.extend(robots.iter().map(|(p, _)| *p));
-    bfr.len() == robots.len()
+    robots.iter().all(|(p, _)| bfr.insert(*p))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::io::{self, Write};
     use test_case::test_case;
+    use utils::grid::Grid;
+
+    /// Number of robots on each tile after `seconds`.
+    fn snapshot(input: &Puzzle, seconds: usize, width: usize, height: usize) -> Grid<usize> {
+        let mut robots = input.0.clone();
+        move_bots(&mut robots, seconds, width, height);
+        TorusGrid::occupancy(width, height, robots.iter().map(|(p, _)| *p)).into_inner()
+    }
+
+    /// Dump the tiles after `seconds` as a PBM image, tiles holding robots are black.
+    fn write_frame<W: Write>(
+        input: &Puzzle,
+        seconds: usize,
+        width: usize,
+        height: usize,
+        out: W,
+    ) -> io::Result<()> {
+        snapshot(input, seconds, width, height).write_pbm(out, |&count| count > 0)
+    }
 
     const TEST: &str = "p=0,4 v=3,-3
 p=6,3 v=-1,-3
@@ -131,6 +118,33 @@ p=9,5 v=-3,-3";
         solve_part1_dim(&input_generator(input), 100, 11, 7)
     }
 
+    #[test]
+    fn part1_snapshot() {
+        let grid = snapshot(&input_generator(TEST), 100, 11, 7);
+        assert_eq!(grid[&Position { x: 6, y: 0 }], 2);
+        assert_eq!(grid[&Position { x: 9, y: 0 }], 1);
+        assert_eq!(grid[&Position { x: 0, y: 2 }], 1);
+        assert_eq!(grid.positions().map(|(_, c)| c).sum::<usize>(), 12);
+    }
+
+    #[test]
+    fn frame() {
+        let mut out = Vec::new();
+        write_frame(&input_generator(TEST), 0, 11, 7, &mut out).unwrap();
+        assert!(out.starts_with(b"P4\n11 7\n"));
+        assert_eq!(out.len(), 8 + 7 * 2);
+    }
+
+    // Run with `cargo test day14 -- --ignored` to look at the tree.
+    #[test]
+    #[ignore = "needs the puzzle input, writes target/day14.pbm"]
+    fn dump_tree_frame() -> io::Result<()> {
+        let puzzle = input_generator(&std::fs::read_to_string("input/2024/day14.txt")?);
+        let seconds = solve_part2(&puzzle);
+        let file = std::fs::File::create("target/day14.pbm")?;
+        write_frame(&puzzle, seconds, 101, 103, io::BufWriter::new(file))
+    }
+
     // #[test_case(TEST => 80)]
     // fn part2(input: &str) -> usize {
     //     solve_part2(&input_generator(input))